@@ -0,0 +1,648 @@
+use clap::builder::PossibleValuesParser;
+use clap::{Args, Parser, Subcommand};
+use maze_core::{
+    analyze, difficulty_range, farthest_cell, from_json, generate_with_difficulty, import_png,
+    paint_terrain, parse_maz, parse_micromouse_text, parse_text, path_cost, render_directions,
+    render_gif, render_maz, render_micromouse_text, render_pdf, render_png, render_svg,
+    render_text, run_benchmark, runs_csv, solve, summarize, summary_csv, summary_table, to_json,
+    AnimationOptions, BenchmarkOptions, Colours, Generator, ImportOptions, MazeError, PdfOptions,
+    RasterOptions, SolverRun, SvgOptions, TextOptions, TextStyle, WilsonMaze, SOLVERS, THEMES,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Generate and solve mazes without the desktop app.
+#[derive(Parser)]
+#[command(name = "maze-cli", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a maze, optionally solve it, and print it as ASCII art,
+    /// svg, png, json or an animated gif of the solver.
+    Generate(GenerateArgs),
+    /// Generate a printable pdf worksheet with one or more mazes.
+    Worksheet(WorksheetArgs),
+    /// Read a maze from a file, optionally solve it, and print it in any of
+    /// the formats of generate.
+    Import(ImportArgs),
+    /// Time every solver on many generated mazes and summarize the time,
+    /// visited cells and path length.
+    Bench(BenchArgs),
+}
+
+/// Options shared by all commands that generate a maze.
+#[derive(Args)]
+struct MazeArgs {
+    /// Number of rows.
+    #[arg(long, default_value_t = 10)]
+    rows: usize,
+
+    /// Number of columns.
+    #[arg(long, default_value_t = 10)]
+    cols: usize,
+
+    /// Generation algorithm (wilson, backtracker, braid).
+    #[arg(long, default_value = "wilson")]
+    algorithm: String,
+
+    /// Seed for the generator. A random seed is used if not given.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Start cell as row,col.
+    #[arg(long, value_parser = parse_cell, default_value = "0,0")]
+    start: (usize, usize),
+
+    /// End cell as row,col. Defaults to the bottom right cell.
+    #[arg(long, value_parser = parse_cell)]
+    end: Option<(usize, usize)>,
+
+    /// Put the end at the cell that is the most steps away from the start.
+    #[arg(long, conflicts_with = "end")]
+    end_farthest: bool,
+
+    /// Another exit as row,col, can be given several times. The solvers
+    /// stop at the end or exit they reach first.
+    #[arg(long = "exit", value_parser = parse_cell)]
+    exits: Vec<(usize, usize)>,
+
+    /// Only keep mazes with a difficulty score in this range, as easy,
+    /// medium, hard or min-max (0 to 100). Start and end are placed at
+    /// random, and --start and --end are ignored.
+    #[arg(long, value_parser = parse_difficulty)]
+    difficulty: Option<(f32, f32)>,
+
+    /// How many mazes to try for --difficulty before giving up.
+    #[arg(long, default_value_t = 1000)]
+    attempts: usize,
+
+    /// Number of mud and water regions to paint onto the maze. Stepping
+    /// onto mud or water costs more, see the dijkstra solver.
+    #[arg(long, default_value_t = 0)]
+    terrain: usize,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    #[command(flatten)]
+    maze: MazeArgs,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct ImportArgs {
+    /// Maze to read, as a png image, json, micromouse .maz or text. The
+    /// format is taken from the file extension. Text with `o` posts is read
+    /// as a micromouse maze.
+    input: PathBuf,
+
+    /// How much walls in an image have to differ from the background, in
+    /// luminance (0 - 255).
+    #[arg(long, default_value_t = 64)]
+    threshold: u8,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Options for solving a maze and writing it out.
+#[derive(Args)]
+struct OutputArgs {
+    /// Solver to run.
+    #[arg(long, value_parser = solver_names())]
+    solver: Option<String>,
+
+    /// Output format (text, svg, png, json, gif, maz, micromouse, directions).
+    #[arg(long, default_value = "text", value_parser = parse_format)]
+    format: Format,
+
+    /// Text style (ascii, unicode).
+    #[arg(long, default_value = "ascii", value_parser = parse_style)]
+    style: TextStyle,
+
+    /// Also mark the cells visited by the solver.
+    #[arg(long)]
+    visited: bool,
+
+    /// Print metrics of the maze, like dead ends and tortuosity.
+    #[arg(long)]
+    metrics: bool,
+
+    #[command(flatten)]
+    image: ImageArgs,
+
+    #[command(flatten)]
+    animation: AnimationArgs,
+
+    /// Write the maze to this file instead of stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct WorksheetArgs {
+    #[command(flatten)]
+    maze: MazeArgs,
+
+    /// Number of mazes. Each maze uses the seed after the previous one.
+    #[arg(long, default_value_t = 1)]
+    count: usize,
+
+    /// Number of mazes on each page.
+    #[arg(long, default_value_t = 1)]
+    per_page: usize,
+
+    /// Add answer pages with the shortest path.
+    #[arg(long)]
+    answers: bool,
+
+    /// Title printed on every page.
+    #[arg(long, default_value = "Mazes")]
+    title: String,
+
+    /// Write the pdf to this file instead of stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Maze sizes as RxC, separated by commas.
+    #[arg(
+        long,
+        value_parser = parse_size,
+        value_delimiter = ',',
+        default_value = "10x10,50x50,100x100"
+    )]
+    sizes: Vec<(usize, usize)>,
+
+    /// Generation algorithms, separated by commas.
+    #[arg(
+        long,
+        value_parser = parse_generator,
+        value_delimiter = ',',
+        default_value = "wilson,backtracker,braid"
+    )]
+    generators: Vec<Generator>,
+
+    /// Solvers, separated by commas. All solvers if not given.
+    #[arg(long, value_parser = solver_names(), value_delimiter = ',')]
+    solvers: Vec<String>,
+
+    /// Number of mazes for each size and generator.
+    #[arg(long, default_value_t = 20)]
+    count: usize,
+
+    /// Seed of the first maze. A random seed is used if not given.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Print the summary as csv instead of a table.
+    #[arg(long)]
+    csv: bool,
+
+    /// Also write every single run as csv to this file.
+    #[arg(long)]
+    runs: Option<PathBuf>,
+
+    /// Write the summary to this file instead of stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// Options shared by all image formats.
+#[derive(Args)]
+struct ImageArgs {
+    /// Colour theme for images (dark, print). Imported images are
+    /// expected to use the same colours for the start and end markers.
+    #[arg(long, default_value = "dark", value_parser = parse_theme)]
+    theme: Colours,
+
+    /// Size of a cell in px.
+    #[arg(long, default_value_t = 30)]
+    cell_size: usize,
+
+    /// Thickness of the walls in px.
+    #[arg(long, default_value_t = 2)]
+    wall_thickness: usize,
+
+    /// Margin around the maze in px.
+    #[arg(long, default_value_t = 10)]
+    margin: usize,
+
+    /// Colour cells by their distance from the start cell (png only).
+    #[arg(long)]
+    gradient: bool,
+}
+
+/// Options for animated gifs of a solver run.
+#[derive(Args)]
+struct AnimationArgs {
+    /// Frames per second.
+    #[arg(long, default_value_t = 25)]
+    fps: u32,
+
+    /// Number of frames each solver step is shown for.
+    #[arg(long, default_value_t = 1)]
+    frames_per_step: u32,
+
+    /// Seconds the solution is shown for at the end.
+    #[arg(long, default_value_t = 2)]
+    hold: u32,
+}
+
+impl ImageArgs {
+    fn raster_options<'a>(
+        &self,
+        solution: Option<&'a HashSet<(usize, usize)>>,
+        visited: Option<&'a HashSet<(usize, usize)>>,
+    ) -> RasterOptions<'a> {
+        return RasterOptions {
+            cell_size: self.cell_size,
+            wall_thickness: self.wall_thickness,
+            margin: self.margin,
+            colours: self.theme,
+            solution: solution,
+            visited: visited,
+            gradient: self.gradient,
+        };
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Text,
+    Svg,
+    Png,
+    Json,
+    Gif,
+    Maz,
+    Micromouse,
+    Directions,
+}
+
+/// The names in `SOLVERS`, so that the help always lists every solver.
+fn solver_names() -> PossibleValuesParser {
+    return PossibleValuesParser::new(SOLVERS.map(|(name, _)| name));
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value {
+        "text" => return Ok(Format::Text),
+        "svg" => return Ok(Format::Svg),
+        "png" => return Ok(Format::Png),
+        "json" => return Ok(Format::Json),
+        "gif" => return Ok(Format::Gif),
+        "maz" => return Ok(Format::Maz),
+        "micromouse" => return Ok(Format::Micromouse),
+        "directions" => return Ok(Format::Directions),
+        _ => {
+            return Err(format!(
+                "expected text, svg, png, json, gif, maz, micromouse or directions but got '{}'",
+                value
+            ))
+        }
+    }
+}
+
+fn parse_theme(value: &str) -> Result<Colours, String> {
+    return Colours::from_name(value).ok_or(format!(
+        "expected one of {} but got '{}'",
+        THEMES.join(", "),
+        value
+    ));
+}
+
+fn parse_cell(value: &str) -> Result<(usize, usize), String> {
+    let (row, col) = value
+        .split_once(',')
+        .ok_or(format!("expected row,col but got '{}'", value))?;
+
+    let row = row.trim().parse::<usize>().map_err(|e| e.to_string())?;
+    let col = col.trim().parse::<usize>().map_err(|e| e.to_string())?;
+
+    return Ok((row, col));
+}
+
+fn parse_difficulty(value: &str) -> Result<(f32, f32), String> {
+    if let Some(range) = difficulty_range(value) {
+        return Ok(range);
+    }
+
+    let invalid = || format!("expected easy, medium, hard or min-max but got '{}'", value);
+    let (min, max) = value.split_once('-').ok_or_else(invalid)?;
+    let min = min.trim().parse::<f32>().map_err(|_| invalid())?;
+    let max = max.trim().parse::<f32>().map_err(|_| invalid())?;
+
+    return Ok((min, max));
+}
+
+fn parse_size(value: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("expected RxC but got '{}'", value);
+    let (rows, cols) = value.split_once('x').ok_or_else(invalid)?;
+    let rows = rows.trim().parse::<usize>().map_err(|_| invalid())?;
+    let cols = cols.trim().parse::<usize>().map_err(|_| invalid())?;
+
+    if rows == 0 || cols == 0 || rows * cols < 2 {
+        return Err(format!("a maze needs at least two cells, got '{}'", value));
+    }
+
+    return Ok((rows, cols));
+}
+
+fn parse_generator(value: &str) -> Result<Generator, String> {
+    return Generator::from_name(value).map_err(|e| e.to_string());
+}
+
+fn parse_style(value: &str) -> Result<TextStyle, String> {
+    match value {
+        "ascii" => return Ok(TextStyle::Ascii),
+        "unicode" => return Ok(TextStyle::Unicode),
+        _ => return Err(format!("expected ascii or unicode but got '{}'", value)),
+    }
+}
+
+impl MazeArgs {
+    fn build(&self) -> Result<WilsonMaze, MazeError> {
+        return self.build_seeded(self.seed.unwrap_or_else(rand::random));
+    }
+
+    fn build_seeded(&self, seed: u64) -> Result<WilsonMaze, MazeError> {
+        let mut maze = self.generate_seeded(seed)?;
+
+        maze.exits = self.exits.clone();
+        maze.check_ends()?;
+
+        if self.terrain > 0 {
+            // Seeded on its own, so the passages are the same with or
+            // without terrain.
+            let mut rng = StdRng::seed_from_u64(maze.seed.unwrap_or(seed));
+            paint_terrain(&mut maze, self.terrain, &mut rng);
+        }
+
+        return Ok(maze);
+    }
+
+    fn generate_seeded(&self, seed: u64) -> Result<WilsonMaze, MazeError> {
+        let generator = Generator::from_name(&self.algorithm)?;
+
+        if let Some(range) = self.difficulty {
+            let mut rng = StdRng::seed_from_u64(seed);
+            return generate_with_difficulty(
+                self.rows,
+                self.cols,
+                generator,
+                range,
+                self.attempts,
+                &mut rng,
+            );
+        }
+
+        let end = self
+            .end
+            .unwrap_or((self.rows.saturating_sub(1), self.cols.saturating_sub(1)));
+
+        let mut maze = WilsonMaze::try_new(self.rows, self.cols, self.start, end)?;
+        maze.generate_seeded(generator, seed)?;
+
+        if self.end_farthest {
+            maze.end_cell = farthest_cell(&maze, maze.start_cell).0;
+        }
+
+        return Ok(maze);
+    }
+}
+
+fn generate(args: &GenerateArgs) -> Result<(), Box<dyn Error>> {
+    let maze = args.maze.build()?;
+    eprintln!(
+        "Generated {}x{} maze with {} (seed {}).",
+        maze.rows,
+        maze.cols,
+        args.maze.algorithm,
+        maze.seed.unwrap()
+    );
+
+    return write_maze(&maze, &args.output);
+}
+
+fn import(args: &ImportArgs) -> Result<(), Box<dyn Error>> {
+    let extension = args
+        .input
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let maze = match extension.as_str() {
+        "png" => {
+            let options = ImportOptions {
+                colours: args.output.image.theme,
+                threshold: args.threshold,
+            };
+            import_png(&std::fs::read(&args.input)?, &options)?
+        }
+        "json" => from_json(&std::fs::read_to_string(&args.input)?)?.0,
+        "maz" => parse_maz(&std::fs::read(&args.input)?)?,
+        _ => {
+            let text = std::fs::read_to_string(&args.input)?;
+            match text.trim_start().starts_with('o') {
+                true => parse_micromouse_text(&text)?,
+                false => parse_text(&text)?.maze,
+            }
+        }
+    };
+    eprintln!(
+        "Read {}x{} maze from {} (start {:?}, end {:?}).",
+        maze.rows,
+        maze.cols,
+        args.input.display(),
+        maze.start_cell,
+        maze.end_cell
+    );
+    if !maze.exits.is_empty() {
+        eprintln!("Other exits: {:?}.", maze.exits);
+    }
+
+    return write_maze(&maze, &args.output);
+}
+
+fn write_maze(maze: &WilsonMaze, args: &OutputArgs) -> Result<(), Box<dyn Error>> {
+    if args.metrics {
+        for (label, value) in analyze(maze).rows() {
+            eprintln!("{:<18}{:>10}", label, value);
+        }
+    }
+
+    let run = match &args.solver {
+        Some(solver) => {
+            let solution = solve(maze, solver)?;
+            eprintln!(
+                "Solved with {} in {} iterations, solution has {} steps.",
+                solver,
+                solution.visited.len(),
+                solution.steps()
+            );
+            if maze.has_terrain() {
+                eprintln!("The solution costs {}.", path_cost(maze, &solution.path));
+            }
+            Some(SolverRun {
+                solver: solver.clone(),
+                solution: solution,
+            })
+        }
+        None => None,
+    };
+
+    let path = run.as_ref().map(|run| run.solution.path_cells());
+    let solution = path.as_ref();
+    let visited: Option<HashSet<(usize, usize)>> = run
+        .as_ref()
+        .filter(|_| args.visited)
+        .map(|run| run.solution.visited.iter().cloned().collect());
+
+    let bytes = match args.format {
+        Format::Text => {
+            let options = TextOptions {
+                style: args.style,
+                solution: solution,
+                visited: visited.as_ref(),
+            };
+            render_text(maze, &options).into_bytes()
+        }
+        Format::Svg => {
+            let options = SvgOptions {
+                cell_size: args.image.cell_size as f32,
+                wall_thickness: args.image.wall_thickness as f32,
+                margin: args.image.margin as f32,
+                colours: args.image.theme,
+                solution: solution,
+                visited: visited.as_ref(),
+            };
+            render_svg(maze, &options).into_bytes()
+        }
+        Format::Png => render_png(maze, &args.image.raster_options(solution, visited.as_ref()))?,
+        Format::Json => to_json(maze, run.as_ref())?.into_bytes(),
+        Format::Gif => {
+            let run = run
+                .as_ref()
+                .ok_or("an animated gif needs a solver, pass --solver")?;
+            let options = AnimationOptions {
+                raster: args.image.raster_options(None, None),
+                fps: args.animation.fps,
+                frames_per_step: args.animation.frames_per_step,
+                hold_seconds: args.animation.hold,
+            };
+            render_gif(maze, &run.solution, &options)?
+        }
+        Format::Maz => render_maz(maze)?,
+        Format::Micromouse => render_micromouse_text(maze).into_bytes(),
+        Format::Directions => {
+            let run = run
+                .as_ref()
+                .ok_or("directions need a solver, pass --solver")?;
+            render_directions(&run.solution).into_bytes()
+        }
+    };
+
+    return write_output(args.output.as_ref(), &bytes);
+}
+
+fn worksheet(args: &WorksheetArgs) -> Result<(), Box<dyn Error>> {
+    let seed = args.maze.seed.unwrap_or_else(rand::random);
+    let mazes = (0..args.count as u64)
+        .map(|i| args.maze.build_seeded(seed.wrapping_add(i)))
+        .collect::<Result<Vec<WilsonMaze>, MazeError>>()?;
+    eprintln!(
+        "Generated {} {}x{} mazes with {} (seeds {} to {}).",
+        mazes.len(),
+        args.maze.rows,
+        args.maze.cols,
+        args.maze.algorithm,
+        seed,
+        seed.wrapping_add(args.count.saturating_sub(1) as u64)
+    );
+
+    let options = PdfOptions {
+        title: &args.title,
+        mazes_per_page: args.per_page,
+        answer_key: args.answers,
+        ..Default::default()
+    };
+
+    return write_output(args.output.as_ref(), &render_pdf(&mazes, &options)?);
+}
+
+fn bench(args: &BenchArgs) -> Result<(), Box<dyn Error>> {
+    let solvers = match args.solvers.is_empty() {
+        true => SOLVERS.iter().map(|(name, _)| name.to_string()).collect(),
+        false => args.solvers.clone(),
+    };
+    let options = BenchmarkOptions {
+        sizes: args.sizes.clone(),
+        generators: args.generators.clone(),
+        solvers: solvers,
+        mazes: args.count,
+        seed: args.seed.unwrap_or_else(rand::random),
+    };
+
+    eprintln!(
+        "Running {} solvers on {} mazes for each of {} sizes and {} generators (seeds from {}).",
+        options.solvers.len(),
+        options.mazes,
+        options.sizes.len(),
+        options.generators.len(),
+        options.seed
+    );
+    let runs = run_benchmark(&options)?;
+
+    if let Some(path) = &args.runs {
+        std::fs::write(path, runs_csv(&runs))?;
+    }
+
+    let summaries = summarize(&runs);
+    let summary = match args.csv {
+        true => summary_csv(&summaries),
+        false => summary_table(&summaries),
+    };
+
+    return write_output(args.output.as_ref(), summary.as_bytes());
+}
+
+fn write_output(output: Option<&PathBuf>, bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+    match output {
+        Some(path) => std::fs::write(path, bytes)?,
+        None => std::io::stdout().write_all(bytes)?,
+    }
+
+    return Ok(());
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Command::Generate(args) => generate(args),
+        Command::Worksheet(args) => worksheet(args),
+        Command::Import(args) => import(args),
+        Command::Bench(args) => bench(args),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    return ExitCode::SUCCESS;
+}
@@ -0,0 +1,124 @@
+//! Compares the flat bitmask grid against the previous representation,
+//! where each cell was a `HashSet<Direction>`, on a 1000x1000 maze.
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::collections::HashSet;
+use std::hint::black_box;
+
+use maze_core::{direction_reverse, get_bfs_solution, Direction, Grid, Priority, WilsonMaze};
+
+const SIZE: usize = 1000;
+
+/// The grid representation used before `Grid` was introduced.
+fn hashset_grid(rows: usize, cols: usize) -> Vec<Vec<HashSet<Direction>>> {
+    return vec![vec![HashSet::new(); cols]; rows];
+}
+
+/// All carving steps of a generated maze, so that both representations
+/// can be built from exactly the same set of passages.
+fn carves(maze: &WilsonMaze) -> Vec<((usize, usize), Direction)> {
+    let mut carves = vec![];
+
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            for direction in [Direction::Down, Direction::Right] {
+                if maze.grid.has_passage(row, col, &direction) {
+                    carves.push(((row, col), direction));
+                }
+            }
+        }
+    }
+
+    return carves;
+}
+
+fn bench_grid(c: &mut Criterion) {
+    let mut maze = WilsonMaze::new(SIZE, SIZE, (0, 0), (SIZE - 1, SIZE - 1));
+    maze.generate().unwrap();
+    let carves = carves(&maze);
+
+    let mut group = c.benchmark_group("grid_1000x1000");
+    group.sample_size(10);
+
+    group.bench_function("carve/hashset", |b| {
+        b.iter(|| {
+            let mut grid = hashset_grid(SIZE, SIZE);
+            for ((row, col), direction) in &carves {
+                let (nrow, ncol) = match direction {
+                    Direction::Down => (row + 1, *col),
+                    _ => (*row, col + 1),
+                };
+                grid[*row][*col].insert(*direction);
+                grid[nrow][ncol].insert(direction_reverse(direction));
+            }
+            black_box(grid)
+        })
+    });
+
+    group.bench_function("carve/bitmask", |b| {
+        b.iter(|| {
+            let mut grid = Grid::new(SIZE, SIZE);
+            for ((row, col), direction) in &carves {
+                grid.carve(*row, *col, direction);
+            }
+            black_box(grid)
+        })
+    });
+
+    let mut hashset = hashset_grid(SIZE, SIZE);
+    for ((row, col), direction) in &carves {
+        let (nrow, ncol) = match direction {
+            Direction::Down => (row + 1, *col),
+            _ => (*row, col + 1),
+        };
+        hashset[*row][*col].insert(*direction);
+        hashset[nrow][ncol].insert(direction_reverse(direction));
+    }
+
+    group.bench_function("lookup/hashset", |b| {
+        b.iter(|| {
+            let mut open = 0;
+            for cells in &hashset {
+                for cell in cells {
+                    for direction in Direction::as_list() {
+                        open += cell.contains(&direction) as usize;
+                    }
+                }
+            }
+            black_box(open)
+        })
+    });
+
+    group.bench_function("lookup/bitmask", |b| {
+        b.iter(|| {
+            let mut open = 0;
+            for row in 0..SIZE {
+                for col in 0..SIZE {
+                    for direction in Direction::as_list() {
+                        open += maze.grid.has_passage(row, col, &direction) as usize;
+                    }
+                }
+            }
+            black_box(open)
+        })
+    });
+
+    group.bench_function("generate", |b| {
+        b.iter_batched(
+            || WilsonMaze::new(SIZE, SIZE, (0, 0), (SIZE - 1, SIZE - 1)),
+            |mut maze| {
+                maze.generate().unwrap();
+                maze
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("bfs_solve", |b| {
+        b.iter(|| black_box(get_bfs_solution(&maze, Priority::Disabled)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_grid);
+criterion_main!(benches);
@@ -0,0 +1,34 @@
+//! Compares the solvers on the same mazes, for every generator and a few
+//! sizes. Run the `bench` command of maze-cli for visited cells and path
+//! lengths over many mazes.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+
+use maze_core::{solve, Generator, WilsonMaze, GENERATORS, SOLVERS};
+
+const SIZES: [usize; 3] = [50, 200, 500];
+const SEED: u64 = 42;
+
+fn bench_solvers(c: &mut Criterion) {
+    for (name, _) in GENERATORS {
+        let generator = Generator::from_name(name).unwrap();
+        let mut group = c.benchmark_group(format!("solvers/{}", name));
+        group.sample_size(10);
+
+        for size in SIZES {
+            let mut maze = WilsonMaze::new(size, size, (0, 0), (size - 1, size - 1));
+            maze.generate_seeded(generator, SEED).unwrap();
+
+            for (solver, _) in SOLVERS {
+                group.bench_with_input(BenchmarkId::new(solver, size), &maze, |b, maze| {
+                    b.iter(|| black_box(solve(maze, solver).unwrap()))
+                });
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_solvers);
+criterion_main!(benches);
@@ -0,0 +1,297 @@
+use rand::Rng;
+
+use crate::{distance_field, Generator, MazeError, WilsonMaze};
+
+/// Named difficulty ranges, as (name, min, max).
+pub const DIFFICULTIES: [(&str, f32, f32); 3] = [
+    ("easy", 0.0, 40.0),
+    ("medium", 40.0, 70.0),
+    ("hard", 70.0, 100.0),
+];
+
+/// Numbers that describe the shape of a maze, see `analyze`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MazeMetrics {
+    pub cells: usize,
+    /// Cells with a single passage.
+    pub dead_ends: usize,
+    /// Cells with three or four passages.
+    pub junctions: usize,
+    /// Cells with exactly two passages.
+    pub corridors: usize,
+    /// Most cells in a row that are all corridors.
+    pub longest_corridor: usize,
+    /// Average number of ways on from a cell on the solution, not counting
+    /// the way back: the next cell and the side branches that leave the
+    /// solution there. One means that there are no choices to make.
+    pub branching_factor: f32,
+    /// Cells on the shortest path from start to the nearest end, if there
+    /// is one.
+    pub solution_length: Option<usize>,
+    /// Average length of the branches that end in a dead end, up to the
+    /// first junction or the solution. Branches past the end are left out.
+    /// Mazes with a high river factor have few but long side passages that
+    /// "flow" like a river, ones with a low factor have many short ones.
+    pub river_factor: f32,
+    /// Steps along the solution divided by the manhattan distance from start
+    /// to end. One for a straight line.
+    pub tortuosity: Option<f32>,
+    /// Score from 0 to 100, see `difficulty`.
+    pub difficulty: f32,
+}
+
+impl MazeMetrics {
+    /// Labels and formatted values, in the order they should be shown.
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let optional = |value: Option<String>| value.unwrap_or("-".to_string());
+
+        return vec![
+            ("Cells", self.cells.to_string()),
+            ("Dead ends", self.dead_ends.to_string()),
+            ("Junctions", self.junctions.to_string()),
+            ("Corridor cells", self.corridors.to_string()),
+            ("Longest corridor", self.longest_corridor.to_string()),
+            ("Branching factor", format!("{:.2}", self.branching_factor)),
+            (
+                "Solution length",
+                optional(self.solution_length.map(|l| l.to_string())),
+            ),
+            ("River factor", format!("{:.2}", self.river_factor)),
+            (
+                "Tortuosity",
+                optional(self.tortuosity.map(|t| format!("{:.2}", t))),
+            ),
+            ("Difficulty", format!("{:.0}", self.difficulty)),
+        ];
+    }
+}
+
+/// Cells connected to the cell by a passage.
+fn linked(maze: &WilsonMaze, (row, col): (usize, usize)) -> Vec<(usize, usize)> {
+    return maze
+        .grid
+        .passages(row, col)
+        .filter_map(|direction| maze.grid.neighbor(row, col, &direction))
+        .collect();
+}
+
+/// Compute the metrics of a maze. The solution is the shortest path, so
+/// the numbers do not depend on the solver.
+pub fn analyze(maze: &WilsonMaze) -> MazeMetrics {
+    let cells = maze.rows * maze.cols;
+    let index = |(row, col): (usize, usize)| row * maze.cols + col;
+
+    let mut degree: Vec<usize> = vec![0; cells];
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            degree[index((row, col))] = maze.grid.passages(row, col).count();
+        }
+    }
+
+    let dead_ends = degree.iter().filter(|d| **d == 1).count();
+    let junctions = degree.iter().filter(|d| **d >= 3).count();
+    let corridors = degree.iter().filter(|d| **d == 2).count();
+
+    // Corridor cells form chains (or loops) between junctions and dead
+    // ends. Each chain is counted once.
+    let mut seen: Vec<bool> = vec![false; cells];
+    let mut longest_corridor = 0;
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            if degree[index((row, col))] != 2 || seen[index((row, col))] {
+                continue;
+            }
+
+            let mut length = 0;
+            let mut stack = vec![(row, col)];
+            seen[index((row, col))] = true;
+
+            while let Some(cell) = stack.pop() {
+                length += 1;
+                for next in linked(maze, cell) {
+                    if degree[index(next)] == 2 && !seen[index(next)] {
+                        seen[index(next)] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+
+            longest_corridor = std::cmp::max(longest_corridor, length);
+        }
+    }
+
+    // Follow decreasing distances back from the nearest end to find a
+    // shortest path.
+    let distances = distance_field(maze, maze.start_cell);
+    let end_cell = maze
+        .ends()
+        .into_iter()
+        .min_by_key(|end| distances[index(*end)].unwrap_or(usize::MAX))
+        .unwrap();
+    let mut solution: Vec<(usize, usize)> = vec![];
+    if let Some(end_distance) = distances[index(end_cell)] {
+        let mut cell = end_cell;
+        solution.push(cell);
+
+        for distance in (0..end_distance).rev() {
+            cell = linked(maze, cell)
+                .into_iter()
+                .find(|c| distances[index(*c)] == Some(distance))
+                .unwrap();
+            solution.push(cell);
+        }
+    }
+
+    // Walk from every dead end off the solution until the first junction or
+    // the solution. Branches that join the solution at the end are left
+    // out, a solver stops there before it could take them.
+    let mut on_solution: Vec<bool> = vec![false; cells];
+    for cell in &solution {
+        on_solution[index(*cell)] = true;
+    }
+    let mut side_branches = 0;
+    let mut branch_cells = 0;
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            if degree[index((row, col))] != 1 || on_solution[index((row, col))] {
+                continue;
+            }
+
+            let mut length = 0;
+            let mut previous = None;
+            let mut cell = (row, col);
+            while degree[index(cell)] < 3 && !on_solution[index(cell)] {
+                length += 1;
+                match linked(maze, cell)
+                    .into_iter()
+                    .find(|c| Some(*c) != previous)
+                {
+                    Some(next) => {
+                        previous = Some(cell);
+                        cell = next;
+                    }
+                    None => break,
+                }
+                if cell == (row, col) {
+                    break;
+                }
+            }
+
+            if on_solution[index(cell)] && cell == end_cell {
+                continue;
+            }
+            side_branches += 1;
+            branch_cells += length;
+        }
+    }
+    let river_factor = match side_branches {
+        0 => 0.0,
+        _ => branch_cells as f32 / side_branches as f32,
+    };
+
+    let steps = solution.len().saturating_sub(1);
+    let branching_factor = match steps {
+        0 => 0.0,
+        _ => {
+            // Every cell but the end: the way on, and the side branches
+            // that leave the solution there.
+            let choices: usize = solution[1..]
+                .iter()
+                .map(|cell| {
+                    let sides = linked(maze, *cell)
+                        .into_iter()
+                        .filter(|c| !on_solution[index(*c)])
+                        .count();
+                    1 + sides
+                })
+                .sum();
+            choices as f32 / steps as f32
+        }
+    };
+
+    let manhattan = maze.start_cell.0.abs_diff(end_cell.0) + maze.start_cell.1.abs_diff(end_cell.1);
+    let tortuosity = match (solution.is_empty(), manhattan) {
+        (true, _) => None,
+        (false, 0) => Some(1.0),
+        (false, _) => Some(steps as f32 / manhattan as f32),
+    };
+
+    // No maze takes more than a step per cell on average, also when the
+    // estimate is off for small ones.
+    let effort = match steps {
+        0 => 0.0,
+        _ => steps as f32 * (1.0 + 2.0 * (branching_factor - 1.0).max(0.0) * river_factor),
+    };
+    let effort = effort.min(cells as f32);
+
+    return MazeMetrics {
+        cells: cells,
+        dead_ends: dead_ends,
+        junctions: junctions,
+        corridors: corridors,
+        longest_corridor: longest_corridor,
+        branching_factor: branching_factor,
+        solution_length: (!solution.is_empty()).then_some(solution.len()),
+        river_factor: river_factor,
+        tortuosity: tortuosity,
+        difficulty: difficulty(effort, cells),
+    };
+}
+
+/// Turn the expected effort of solving a maze into a score from 0 to 100.
+///
+/// The effort is the number of steps along the solution, plus the steps
+/// into and back out of the side branches along the way: each step offers
+/// `branching_factor - 1` wrong turns that are `river_factor` cells long
+/// on average. Dividing by the number of cells makes the score comparable
+/// between sizes. A solution that has to cover the whole maze scores 95,
+/// and `analyze` never estimates more effort than that.
+pub fn difficulty(effort: f32, cells: usize) -> f32 {
+    if cells == 0 {
+        return 0.0;
+    }
+
+    return 100.0 * (1.0 - (-3.0 * effort / cells as f32).exp());
+}
+
+/// The (min, max) range of a named difficulty.
+pub fn difficulty_range(name: &str) -> Option<(f32, f32)> {
+    return DIFFICULTIES
+        .iter()
+        .find(|(n, _, _)| *n == name)
+        .map(|(_, min, max)| (*min, *max));
+}
+
+/// Generate mazes with random seeds and start and end cells until one has
+/// a difficulty within `range`. Each attempt generates a new maze, since
+/// the start cell is part of what the generator is seeded with.
+pub fn generate_with_difficulty<R: Rng>(
+    rows: usize,
+    cols: usize,
+    generator: Generator,
+    (min, max): (f32, f32),
+    attempts: usize,
+    rng: &mut R,
+) -> Result<WilsonMaze, MazeError> {
+    if rows * cols < 2 {
+        return Err(MazeError::DifficultyNotFound { attempts: 0 });
+    }
+
+    for _ in 0..attempts {
+        let start = (rng.random_range(0..rows), rng.random_range(0..cols));
+        let mut end = start;
+        while end == start {
+            end = (rng.random_range(0..rows), rng.random_range(0..cols));
+        }
+
+        let mut maze = WilsonMaze::new(rows, cols, start, end);
+        maze.generate_seeded(generator, rng.random())?;
+
+        let score = analyze(&maze).difficulty;
+        if score >= min && score <= max {
+            return Ok(maze);
+        }
+    }
+
+    return Err(MazeError::DifficultyNotFound { attempts: attempts });
+}
@@ -0,0 +1,263 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::{solve, Generator, MazeError, WilsonMaze};
+
+/// What `run_benchmark` generates and solves.
+#[derive(Debug, Clone)]
+pub struct BenchmarkOptions {
+    /// Sizes as (rows, cols).
+    pub sizes: Vec<(usize, usize)>,
+    pub generators: Vec<Generator>,
+    /// Solver names, see `SOLVERS`.
+    pub solvers: Vec<String>,
+    /// Mazes per size and generator. Every solver runs on the same mazes.
+    pub mazes: usize,
+    /// Seed of the first maze, the others use the seeds after it.
+    pub seed: u64,
+}
+
+/// One solver run on one maze.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRun {
+    pub rows: usize,
+    pub cols: usize,
+    pub generator: Generator,
+    pub seed: u64,
+    pub solver: String,
+    pub time: Duration,
+    pub visited: usize,
+    pub path_length: usize,
+}
+
+/// Mean, median and 95th percentile of a series of measurements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub p95: f64,
+}
+
+impl Stats {
+    /// The 95th percentile is the nearest rank, so it is always one of the
+    /// values. All zero for no values.
+    pub fn from_values(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Stats {
+                mean: 0.0,
+                median: 0.0,
+                p95: 0.0,
+            };
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let n = sorted.len();
+
+        let median = match n % 2 {
+            0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+            _ => sorted[n / 2],
+        };
+        let p95 = sorted[((0.95 * n as f64).ceil() as usize).clamp(1, n) - 1];
+
+        return Stats {
+            mean: sorted.iter().sum::<f64>() / n as f64,
+            median: median,
+            p95: p95,
+        };
+    }
+}
+
+/// Statistics over all runs of a solver on one size and generator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkSummary {
+    pub rows: usize,
+    pub cols: usize,
+    pub generator: Generator,
+    pub solver: String,
+    pub runs: usize,
+    /// Wall clock time in milliseconds.
+    pub time_ms: Stats,
+    pub visited: Stats,
+    pub path_length: Stats,
+}
+
+/// Generate `options.mazes` mazes for every size and generator, from the
+/// top left to the bottom right cell, and time every solver on each.
+pub fn run_benchmark(options: &BenchmarkOptions) -> Result<Vec<BenchmarkRun>, MazeError> {
+    let mut runs = vec![];
+
+    for (rows, cols) in &options.sizes {
+        for generator in &options.generators {
+            for i in 0..options.mazes as u64 {
+                let seed = options.seed.wrapping_add(i);
+                let mut maze = WilsonMaze::new(*rows, *cols, (0, 0), (rows - 1, cols - 1));
+                maze.generate_seeded(*generator, seed)?;
+
+                for solver in &options.solvers {
+                    let started = Instant::now();
+                    let solution = solve(&maze, solver)?;
+                    let time = started.elapsed();
+
+                    runs.push(BenchmarkRun {
+                        rows: *rows,
+                        cols: *cols,
+                        generator: *generator,
+                        seed: seed,
+                        solver: solver.clone(),
+                        time: time,
+                        visited: solution.visited.len(),
+                        path_length: solution.path.len(),
+                    });
+                }
+            }
+        }
+    }
+
+    return Ok(runs);
+}
+
+/// Group the runs by size, generator and solver, in the order they first
+/// appear.
+pub fn summarize(runs: &[BenchmarkRun]) -> Vec<BenchmarkSummary> {
+    let mut groups: Vec<Vec<&BenchmarkRun>> = vec![];
+
+    for run in runs {
+        let same = |other: &&BenchmarkRun| {
+            (other.rows, other.cols, other.generator, &other.solver)
+                == (run.rows, run.cols, run.generator, &run.solver)
+        };
+        match groups.iter_mut().find(|group| same(&group[0])) {
+            Some(group) => group.push(run),
+            None => groups.push(vec![run]),
+        }
+    }
+
+    return groups
+        .iter()
+        .map(|group| {
+            let stats = |value: fn(&BenchmarkRun) -> f64| {
+                Stats::from_values(&group.iter().map(|run| value(run)).collect::<Vec<f64>>())
+            };
+
+            BenchmarkSummary {
+                rows: group[0].rows,
+                cols: group[0].cols,
+                generator: group[0].generator,
+                solver: group[0].solver.clone(),
+                runs: group.len(),
+                time_ms: stats(|run| run.time.as_secs_f64() * 1000.0),
+                visited: stats(|run| run.visited as f64),
+                path_length: stats(|run| run.path_length as f64),
+            }
+        })
+        .collect();
+}
+
+/// One line per run, with a header.
+pub fn runs_csv(runs: &[BenchmarkRun]) -> String {
+    let mut csv = "rows,cols,generator,seed,solver,time_ms,visited,path_length\n".to_string();
+
+    for run in runs {
+        writeln!(
+            csv,
+            "{},{},{},{},{},{:.4},{},{}",
+            run.rows,
+            run.cols,
+            run.generator.name(),
+            run.seed,
+            run.solver,
+            run.time.as_secs_f64() * 1000.0,
+            run.visited,
+            run.path_length
+        )
+        .unwrap();
+    }
+
+    return csv;
+}
+
+/// One line per size, generator and solver, with a header.
+pub fn summary_csv(summaries: &[BenchmarkSummary]) -> String {
+    let mut csv = "rows,cols,generator,solver,runs,\
+        time_ms_mean,time_ms_median,time_ms_p95,\
+        visited_mean,visited_median,visited_p95,\
+        path_length_mean,path_length_median,path_length_p95\n"
+        .to_string();
+
+    for s in summaries {
+        write!(
+            csv,
+            "{},{},{},{},{}",
+            s.rows,
+            s.cols,
+            s.generator.name(),
+            s.solver,
+            s.runs
+        )
+        .unwrap();
+        for stats in [s.time_ms, s.visited, s.path_length] {
+            write!(
+                csv,
+                ",{:.4},{:.4},{:.4}",
+                stats.mean, stats.median, stats.p95
+            )
+            .unwrap();
+        }
+        csv.push('\n');
+    }
+
+    return csv;
+}
+
+/// Aligned table of the summaries, for reading in a terminal.
+pub fn summary_table(summaries: &[BenchmarkSummary]) -> String {
+    let header = [
+        "size",
+        "generator",
+        "solver",
+        "runs",
+        "time ms (mean/median/p95)",
+        "visited (mean/median/p95)",
+        "path (mean/median/p95)",
+    ];
+
+    let mut lines: Vec<Vec<String>> = vec![header.iter().map(|h| h.to_string()).collect()];
+    for s in summaries {
+        lines.push(vec![
+            format!("{}x{}", s.rows, s.cols),
+            s.generator.name().to_string(),
+            s.solver.clone(),
+            s.runs.to_string(),
+            format!(
+                "{:.3} / {:.3} / {:.3}",
+                s.time_ms.mean, s.time_ms.median, s.time_ms.p95
+            ),
+            format!(
+                "{:.1} / {:.1} / {:.1}",
+                s.visited.mean, s.visited.median, s.visited.p95
+            ),
+            format!(
+                "{:.1} / {:.1} / {:.1}",
+                s.path_length.mean, s.path_length.median, s.path_length.p95
+            ),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| lines.iter().map(|line| line[i].len()).max().unwrap_or(0))
+        .collect();
+
+    let mut table = String::new();
+    for line in &lines {
+        let cells: Vec<String> = line
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+
+    return table;
+}
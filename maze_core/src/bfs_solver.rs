@@ -0,0 +1,242 @@
+use priority_queue::PriorityQueue;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{MazeError, Solution, WilsonMaze};
+
+/// Parent of each cell reached by `bfs_solve`, the cell it was reached from.
+pub type Parents = HashMap<(usize, usize), (usize, usize)>;
+
+/// Order in which `bfs_solve` visits the reached cells. Only `Disabled`
+/// guarantees a shortest path, the others are heuristics.
+pub enum Priority {
+    /// Plain breadth first search.
+    Disabled,
+    /// Cells far from the nearest end first.
+    Close,
+    /// Cells far from the start and close to the nearest end first.
+    Prio,
+    /// Cells in random order.
+    Random,
+}
+
+#[inline]
+pub fn abs_dist(x: usize, y: usize) -> usize {
+    return std::cmp::max(x, y) - std::cmp::min(x, y);
+}
+
+/// We calculate a priority based on considering the distance
+/// from start cell (lower priority) and end cell (higher priority).
+#[inline]
+pub fn weighted_priority(
+    cell: (usize, usize),
+    start_cell: (usize, usize),
+    end_cell: (usize, usize),
+    max_dist: usize,
+) -> usize {
+    let dx_end = abs_dist(cell.0, end_cell.0);
+    let dy_end = abs_dist(cell.1, end_cell.1);
+
+    let dx_start = abs_dist(cell.0, start_cell.0);
+    let dy_start = abs_dist(cell.1, start_cell.1);
+
+    return max_dist + (dx_start + dy_start) - (dx_end + dy_end);
+}
+
+/// Cells closer to the end cell will get lower priority (very non-ideal).
+#[inline]
+pub fn close_priority(cell: (usize, usize), end_cell: (usize, usize)) -> usize {
+    let dx = abs_dist(end_cell.0, cell.0);
+    let dy = abs_dist(end_cell.1, cell.1);
+
+    return dx + dy;
+}
+
+/// Cells that have been reached but not visited yet. Plain breadth first
+/// search takes them out in the order they were reached, the prioritized
+/// searches take the one with the highest priority first.
+enum Frontier {
+    Fifo(VecDeque<(usize, usize)>),
+    Prioritized(PriorityQueue<(usize, usize), usize>),
+}
+
+impl Frontier {
+    fn push(&mut self, cell: (usize, usize), priority: usize) {
+        match self {
+            Frontier::Fifo(queue) => queue.push_back(cell),
+            Frontier::Prioritized(queue) => {
+                queue.push(cell, priority);
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<(usize, usize)> {
+        match self {
+            Frontier::Fifo(queue) => return queue.pop_front(),
+            Frontier::Prioritized(queue) => return queue.pop().map(|(cell, _)| cell),
+        }
+    }
+}
+
+/// Search from the start cell until one of the ends is visited.
+///
+/// Returns the parent of each reached cell, and all visited cells in the
+/// order they were taken from the frontier, so the last one is the end that
+/// was reached.
+///
+/// Cells are marked as reached when they are added to the frontier and keep
+/// the parent they were first reached from. With `Priority::Disabled` this
+/// is a breadth first search, so the path back from the end is always a
+/// shortest one, to the nearest end. The other priorities are heuristics:
+/// they may visit fewer cells, but in mazes with loops the path they find
+/// can be longer.
+pub fn bfs_solve(
+    maze: &WilsonMaze,
+    priority: Priority,
+) -> Result<(Parents, Vec<(usize, usize)>), MazeError> {
+    let start_cell = maze.start_cell;
+    let ends = maze.ends();
+
+    maze.check_ends()?;
+
+    let mut reached: HashSet<(usize, usize)> = HashSet::new();
+    let mut visit_order: Vec<(usize, usize)> = vec![];
+    let mut path: Parents = HashMap::new();
+
+    let mut frontier = match priority {
+        Priority::Disabled => Frontier::Fifo(VecDeque::new()),
+        _ => Frontier::Prioritized(PriorityQueue::new()),
+    };
+    frontier.push(start_cell, 1);
+    reached.insert(start_cell);
+
+    while let Some(current) = frontier.pop() {
+        visit_order.push(current);
+
+        if maze.is_end(current) {
+            return Ok((path, visit_order));
+        }
+
+        for direction in maze.grid.passages(current.0, current.1) {
+            let (nrow, ncol) = maze
+                .grid
+                .neighbor(current.0, current.1, &direction)
+                .unwrap();
+
+            if reached.insert((nrow, ncol)) {
+                path.insert((nrow, ncol), current);
+
+                let priority = match priority {
+                    Priority::Prio => ends
+                        .iter()
+                        .map(|end| {
+                            weighted_priority((nrow, ncol), start_cell, *end, maze.max_dist())
+                        })
+                        .max()
+                        .unwrap(),
+                    Priority::Close => ends
+                        .iter()
+                        .map(|end| close_priority((nrow, ncol), *end))
+                        .min()
+                        .unwrap(),
+                    Priority::Disabled => 1,
+                    Priority::Random => rand::random_range(1..10),
+                };
+                frontier.push((nrow, ncol), priority);
+            }
+        }
+    }
+
+    return Err(MazeError::NoSolution);
+}
+
+pub fn get_bfs_solution(maze: &WilsonMaze, priority: Priority) -> Result<Solution, MazeError> {
+    let (bfs_path, visited): (Parents, Vec<(usize, usize)>) = bfs_solve(&maze, priority)?;
+
+    let mut s = *visited.last().unwrap();
+    let start = maze.start_cell;
+
+    // Walk back from the end, then turn the path around.
+    let mut path: Vec<(usize, usize)> = vec![s];
+
+    if s == start {
+        return Ok(Solution {
+            path: path,
+            visited: visited,
+        });
+    }
+
+    let mut num = 0;
+
+    while num < bfs_path.len() {
+        s = *bfs_path.get(&s).ok_or(MazeError::NoSolution)?;
+        path.push(s);
+
+        if s == start {
+            path.reverse();
+            return Ok(Solution {
+                path: path,
+                visited: visited,
+            });
+        }
+
+        num += 1;
+    }
+
+    return Err(MazeError::NoSolution);
+}
+
+/// Number of steps from `from` to every cell, stored row by row.
+/// Cells that cannot be reached are None.
+pub fn distance_field(maze: &WilsonMaze, from: (usize, usize)) -> Vec<Option<usize>> {
+    let mut distances: Vec<Option<usize>> = vec![None; maze.rows * maze.cols];
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+
+    distances[from.0 * maze.cols + from.1] = Some(0);
+    queue.push_back(from);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[current.0 * maze.cols + current.1].unwrap();
+
+        for direction in maze.grid.passages(current.0, current.1) {
+            let (nrow, ncol) = maze
+                .grid
+                .neighbor(current.0, current.1, &direction)
+                .unwrap();
+
+            if distances[nrow * maze.cols + ncol].is_none() {
+                distances[nrow * maze.cols + ncol] = Some(distance + 1);
+                queue.push_back((nrow, ncol));
+            }
+        }
+    }
+
+    return distances;
+}
+
+/// The reachable cell that is the most steps away from `from`, and the
+/// number of steps to it. Ties go to the first cell row by row.
+pub fn farthest_cell(maze: &WilsonMaze, from: (usize, usize)) -> ((usize, usize), usize) {
+    let distances = distance_field(maze, from);
+
+    let mut farthest = (from, 0);
+    for (i, distance) in distances.iter().enumerate() {
+        if let Some(distance) = *distance {
+            if distance > farthest.1 {
+                farthest = ((i / maze.cols, i % maze.cols), distance);
+            }
+        }
+    }
+
+    return farthest;
+}
+
+/// The two cells that are the most steps apart, and the number of steps
+/// between them. The farthest cell from the start is one end of the
+/// longest path in a perfect maze, and the farthest cell from that end is
+/// the other. Mazes with loops may have longer paths than this.
+pub fn diameter(maze: &WilsonMaze) -> ((usize, usize), (usize, usize), usize) {
+    let (first, _) = farthest_cell(maze, maze.start_cell);
+    let (second, steps) = farthest_cell(maze, first);
+
+    return (first, second, steps);
+}
@@ -0,0 +1,74 @@
+use crate::{MazeError, Solution, WilsonMaze};
+use std::collections::HashSet;
+
+/// Depth first search from `start`: follow the passages of each cell in
+/// order and back up at dead ends, until one of the ends is reached.
+///
+/// Returns the path to that end, empty if no end can be reached, with all
+/// entered cells in order as the visited cells. The cells on the current
+/// path are kept on an explicit stack instead of the call stack, so long
+/// paths in large mazes do not overflow it.
+pub fn backtrack(maze: &WilsonMaze, start: (usize, usize)) -> Solution {
+    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+    let mut visit_order: Vec<(usize, usize)> = vec![start];
+
+    if maze.is_end(start) {
+        return Solution {
+            path: vec![start],
+            visited: visit_order,
+        };
+    }
+
+    // Each cell on the path, with the passages that have not been tried yet.
+    visited.insert(start);
+    let mut stack = vec![(start, maze.grid.passages(start.0, start.1))];
+
+    while let Some((cell, passages)) = stack.last_mut() {
+        let cell = *cell;
+
+        let direction = match passages.next() {
+            Some(direction) => direction,
+            None => {
+                stack.pop();
+                continue;
+            }
+        };
+
+        let next = maze.grid.neighbor(cell.0, cell.1, &direction).unwrap();
+        if visited.contains(&next) {
+            continue;
+        }
+        visit_order.push(next);
+
+        // Stop at the first end found. In mazes with loops, an end could
+        // otherwise be reached again along another path.
+        if maze.is_end(next) {
+            let mut path: Vec<(usize, usize)> = stack.iter().map(|(cell, _)| *cell).collect();
+            path.push(next);
+            return Solution {
+                path: path,
+                visited: visit_order,
+            };
+        }
+
+        visited.insert(next);
+        stack.push((next, maze.grid.passages(next.0, next.1)));
+    }
+
+    return Solution {
+        path: vec![],
+        visited: visit_order,
+    };
+}
+
+pub fn get_backtrack_solution(maze: &WilsonMaze) -> Result<Solution, MazeError> {
+    maze.check_ends()?;
+
+    let solution = backtrack(maze, maze.start_cell);
+
+    if solution.path.is_empty() {
+        return Err(MazeError::NoSolution);
+    }
+
+    return Ok(solution);
+}
@@ -0,0 +1,64 @@
+use priority_queue::PriorityQueue;
+use std::cmp::Reverse;
+
+use crate::{MazeError, Solution, WilsonMaze};
+
+/// Find the path from the start cell to any of the ends with the lowest
+/// total cost, where stepping onto a cell costs the cost of its terrain.
+/// Cells are visited in order of their cost from the start, so without any
+/// terrain this is the same as a breadth first search.
+pub fn get_dijkstra_solution(maze: &WilsonMaze) -> Result<Solution, MazeError> {
+    let start_cell = maze.start_cell;
+
+    maze.check_ends()?;
+
+    let index = |(row, col): (usize, usize)| row * maze.cols + col;
+
+    let mut costs: Vec<Option<usize>> = vec![None; maze.rows * maze.cols];
+    let mut parents: Vec<Option<(usize, usize)>> = vec![None; maze.rows * maze.cols];
+    let mut done: Vec<bool> = vec![false; maze.rows * maze.cols];
+    let mut visit_order: Vec<(usize, usize)> = vec![];
+
+    // The queue pops the highest priority, so costs are reversed.
+    let mut queue: PriorityQueue<(usize, usize), Reverse<usize>> = PriorityQueue::new();
+    costs[index(start_cell)] = Some(0);
+    queue.push(start_cell, Reverse(0));
+
+    while let Some((current, Reverse(cost))) = queue.pop() {
+        done[index(current)] = true;
+        visit_order.push(current);
+
+        if maze.is_end(current) {
+            let mut path = vec![current];
+            while let Some(parent) = parents[index(*path.last().unwrap())] {
+                path.push(parent);
+            }
+            path.reverse();
+
+            return Ok(Solution {
+                path: path,
+                visited: visit_order,
+            });
+        }
+
+        for direction in maze.grid.passages(current.0, current.1) {
+            let next = maze
+                .grid
+                .neighbor(current.0, current.1, &direction)
+                .unwrap();
+            if done[index(next)] {
+                continue;
+            }
+
+            let next_cost = cost + maze.terrain(next.0, next.1).cost();
+            if costs[index(next)].is_none_or(|c| next_cost < c) {
+                costs[index(next)] = Some(next_cost);
+                parents[index(next)] = Some(current);
+                // Pushing a cell that is already queued updates its priority.
+                queue.push(next, Reverse(next_cost));
+            }
+        }
+    }
+
+    return Err(MazeError::NoSolution);
+}
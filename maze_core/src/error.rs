@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// Errors that can occur when building or solving a maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MazeError {
+    /// The cell (row, col) lies outside of a maze of the given dimensions.
+    OutOfBounds {
+        cell: (usize, usize),
+        rows: usize,
+        cols: usize,
+    },
+    /// The end cell cannot be reached from the start cell.
+    NoSolution,
+    /// There is no solver with the given name.
+    InvalidSolver(String),
+    /// There is no generator with the given name.
+    InvalidGenerator(String),
+    /// A maze could not be read from its text representation.
+    Parse(String),
+    /// A maze could not be written to another file format.
+    Export(String),
+    /// None of the generated mazes had a difficulty in the requested range.
+    DifficultyNotFound { attempts: usize },
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MazeError::OutOfBounds { cell, rows, cols } => write!(
+                f,
+                "Cell ({}, {}) is outside of the {}x{} maze.",
+                cell.0, cell.1, rows, cols
+            ),
+            MazeError::NoSolution => write!(f, "No solution exists."),
+            MazeError::InvalidSolver(name) => write!(f, "Invalid solver method: {}.", name),
+            MazeError::InvalidGenerator(name) => write!(f, "Invalid generator: {}.", name),
+            MazeError::Parse(reason) => write!(f, "Could not parse maze: {}", reason),
+            MazeError::Export(reason) => write!(f, "Could not export maze: {}", reason),
+            MazeError::DifficultyNotFound { attempts } => write!(
+                f,
+                "No maze with a difficulty in range was found in {} attempts.",
+                attempts
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MazeError {}
@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::export::{
+    blend, cell_backgrounds, image_size, rasterize, repaint_cell, Canvas, Colours, RasterOptions,
+    Rgba,
+};
+use crate::{MazeError, Solution, WilsonMaze};
+
+/// Options for `render_gif`. The solution and visited cells of `raster`
+/// are ignored, since they are taken from the solver run instead.
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationOptions<'a> {
+    pub raster: RasterOptions<'a>,
+    /// Frames per second.
+    pub fps: u32,
+    /// Number of frames each solver step is shown for.
+    pub frames_per_step: u32,
+    /// How long the final frame with the solution is shown, in seconds.
+    pub hold_seconds: u32,
+}
+
+impl Default for AnimationOptions<'_> {
+    fn default() -> Self {
+        return Self {
+            raster: RasterOptions {
+                cell_size: 10,
+                ..Default::default()
+            },
+            fps: 25,
+            frames_per_step: 1,
+            hold_seconds: 2,
+        };
+    }
+}
+
+/// Every colour a frame can contain, from the theme colours and the cell
+/// backgrounds: each of the marks can be drawn on top of the others.
+fn palette_colours(colours: &Colours, backgrounds: &[Rgba]) -> Result<Vec<Rgba>, MazeError> {
+    let mut palette: Vec<Rgba> = vec![colours.background];
+    for background in backgrounds {
+        if !palette.contains(background) {
+            palette.push(*background);
+        }
+    }
+
+    for mark in [
+        colours.visited,
+        colours.solution,
+        colours.start,
+        colours.end,
+        colours.wall,
+    ] {
+        for i in 0..palette.len() {
+            let colour = blend(palette[i], mark);
+            if !palette.contains(&colour) {
+                palette.push(colour);
+            }
+        }
+
+        if palette.len() > 256 {
+            return Err(MazeError::Export(
+                "Too many colours for a gif, try without the gradient.".to_string(),
+            ));
+        }
+    }
+
+    return Ok(palette);
+}
+
+/// Write a rectangle of the canvas as the next frame, as palette indices.
+fn write_frame<W: std::io::Write>(
+    encoder: &mut gif::Encoder<W>,
+    canvas: &Canvas,
+    lookup: &HashMap<Rgba, u8>,
+    (x, y, width, height): (usize, usize, usize, usize),
+    delay: u32,
+) -> Result<(), MazeError> {
+    let mut indices: Vec<u8> = Vec::with_capacity(width * height);
+    for py in y..y + height {
+        for px in x..x + width {
+            match lookup.get(&canvas.pixel(px, py)) {
+                Some(index) => indices.push(*index),
+                None => {
+                    return Err(MazeError::Export(format!(
+                        "Colour {:?} is not in the gif palette.",
+                        canvas.pixel(px, py)
+                    )))
+                }
+            }
+        }
+    }
+
+    let mut frame = gif::Frame::from_indexed_pixels(width as u16, height as u16, indices, None);
+    frame.left = x as u16;
+    frame.top = y as u16;
+    frame.delay = delay as u16;
+
+    return encoder
+        .write_frame(&frame)
+        .map_err(|e| MazeError::Export(e.to_string()));
+}
+
+/// Render a solver run as an animated gif. Each frame adds the next cell in
+/// the order the solver visited them, and the last frame shows the solution.
+///
+/// Only a single canvas is kept. The first and the last frame cover the
+/// whole image, the frames in between only the cell that was visited, on
+/// top of the previous frame.
+pub fn render_gif(
+    maze: &WilsonMaze,
+    solution: &Solution,
+    options: &AnimationOptions,
+) -> Result<Vec<u8>, MazeError> {
+    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+    let path = solution.path_cells();
+
+    let empty = RasterOptions {
+        solution: None,
+        visited: None,
+        ..options.raster
+    };
+
+    // Checked before drawing, the canvas of a huge maze may not fit in memory.
+    let (width, height) = image_size(maze, &empty);
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(MazeError::Export(format!(
+            "Image of {}x{} px is too large for a gif.",
+            width, height
+        )));
+    }
+
+    let mut canvas = rasterize(maze, &empty);
+    let backgrounds = cell_backgrounds(maze, &empty);
+
+    let colours = palette_colours(&options.raster.colours, &backgrounds)?;
+    let lookup: HashMap<Rgba, u8> = colours
+        .iter()
+        .enumerate()
+        .map(|(i, colour)| (*colour, i as u8))
+        .collect();
+    let palette: Vec<u8> = colours.iter().flat_map(|c| c[..3].to_vec()).collect();
+
+    // Gif delays are in hundredths of a second.
+    let step_delay = std::cmp::max(
+        100 * options.frames_per_step / std::cmp::max(options.fps, 1),
+        1,
+    );
+    let hold_delay = std::cmp::max(100 * options.hold_seconds, step_delay);
+    let cell = options.raster.cell_size;
+
+    let mut bytes: Vec<u8> = vec![];
+
+    {
+        let mut encoder = gif::Encoder::new(&mut bytes, width as u16, height as u16, &palette)
+            .map_err(|e| MazeError::Export(e.to_string()))?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| MazeError::Export(e.to_string()))?;
+
+        // One frame before the first step, one per step and one with the
+        // solution.
+        write_frame(
+            &mut encoder,
+            &canvas,
+            &lookup,
+            (0, 0, width, height),
+            step_delay,
+        )?;
+
+        for cell_pos in &solution.visited {
+            visited.insert(*cell_pos);
+            let raster = RasterOptions {
+                solution: None,
+                visited: Some(&visited),
+                ..options.raster
+            };
+            repaint_cell(&mut canvas, maze, &raster, &backgrounds, *cell_pos);
+
+            let (x, y) = (
+                options.raster.margin + cell_pos.1 * cell,
+                options.raster.margin + cell_pos.0 * cell,
+            );
+            write_frame(
+                &mut encoder,
+                &canvas,
+                &lookup,
+                (x, y, cell, cell),
+                step_delay,
+            )?;
+        }
+
+        let raster = RasterOptions {
+            solution: Some(&path),
+            visited: Some(&visited),
+            ..options.raster
+        };
+        for cell_pos in &path {
+            repaint_cell(&mut canvas, maze, &raster, &backgrounds, *cell_pos);
+        }
+        write_frame(
+            &mut encoder,
+            &canvas,
+            &lookup,
+            (0, 0, width, height),
+            hold_delay,
+        )?;
+    }
+
+    return Ok(bytes);
+}
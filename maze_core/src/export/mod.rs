@@ -0,0 +1,76 @@
+//! Exporters that turn a maze into other file formats.
+
+mod svg;
+pub use svg::{render_svg, SvgOptions};
+
+mod raster;
+pub use raster::{
+    blend, cell_backgrounds, gradient_colour, image_size, rasterize, repaint_cell, Canvas,
+    RasterOptions,
+};
+
+mod png;
+pub use png::{decode_png, encode_png, render_png};
+
+mod gif;
+pub use gif::{render_gif, AnimationOptions};
+
+mod pdf;
+pub use pdf::{render_pdf, PdfOptions, A4};
+
+/// An RGBA colour.
+pub type Rgba = [u8; 4];
+
+/// Names of the available colour themes.
+pub const THEMES: [&str; 2] = ["dark", "print"];
+
+/// Colours used when exporting a maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colours {
+    pub background: Rgba,
+    pub wall: Rgba,
+    pub solution: Rgba,
+    pub visited: Rgba,
+    pub start: Rgba,
+    pub end: Rgba,
+}
+
+impl Colours {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dark" => return Some(Self::dark()),
+            "print" => return Some(Self::print()),
+            _ => return None,
+        }
+    }
+
+    /// The colours of the app, see main.css.
+    pub fn dark() -> Self {
+        return Self {
+            background: [0x0f, 0x11, 0x16, 0xff],
+            wall: [0x80, 0x80, 0x80, 0xff],
+            solution: [0xe9, 0x56, 0x20, 0xff],
+            visited: [0xe9, 0x56, 0x20, 0x0d],
+            start: [0xff, 0xff, 0xff, 0xff],
+            end: [0xff, 0xff, 0xff, 0xff],
+        };
+    }
+
+    /// Black walls on white, for printing.
+    pub fn print() -> Self {
+        return Self {
+            background: [0xff, 0xff, 0xff, 0xff],
+            wall: [0x00, 0x00, 0x00, 0xff],
+            solution: [0xe9, 0x56, 0x20, 0xff],
+            visited: [0xe9, 0x56, 0x20, 0x33],
+            start: [0x2e, 0x8b, 0x57, 0xff],
+            end: [0xe9, 0x56, 0x20, 0xff],
+        };
+    }
+}
+
+impl Default for Colours {
+    fn default() -> Self {
+        return Self::dark();
+    }
+}
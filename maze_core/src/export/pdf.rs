@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str, TextStr};
+
+use crate::export::{Colours, Rgba};
+use crate::{get_bfs_solution, Direction, MazeError, Priority, WilsonMaze};
+
+/// A4 in pt.
+pub const A4: (f32, f32) = (595.0, 842.0);
+
+const FONT: Name = Name(b"F1");
+const TITLE_SIZE: f32 = 16.0;
+const LABEL_SIZE: f32 = 9.0;
+
+/// Options for `render_pdf`. Sizes are in pt.
+#[derive(Debug, Clone, Copy)]
+pub struct PdfOptions<'a> {
+    pub title: &'a str,
+    pub mazes_per_page: usize,
+    /// Add pages with the solutions after the mazes.
+    pub answer_key: bool,
+    pub page_size: (f32, f32),
+    pub margin: f32,
+    pub wall_thickness: f32,
+    pub colours: Colours,
+}
+
+impl Default for PdfOptions<'_> {
+    fn default() -> Self {
+        return Self {
+            title: "Mazes",
+            mazes_per_page: 1,
+            answer_key: false,
+            page_size: A4,
+            margin: 36.0,
+            wall_thickness: 1.5,
+            colours: Colours::print(),
+        };
+    }
+}
+
+/// The base fonts use their own encoding, so anything that is not ASCII
+/// is replaced.
+fn pdf_text(text: &str) -> String {
+    return text
+        .chars()
+        .map(|c| if c.is_ascii() { c } else { '?' })
+        .collect();
+}
+
+fn set_fill(content: &mut Content, colour: &Rgba) {
+    content.set_fill_rgb(
+        colour[0] as f32 / 255.0,
+        colour[1] as f32 / 255.0,
+        colour[2] as f32 / 255.0,
+    );
+}
+
+fn show_text(content: &mut Content, x: f32, y: f32, size: f32, text: &str) {
+    content
+        .begin_text()
+        .set_font(FONT, size)
+        .next_line(x, y)
+        .show(Str(pdf_text(text).as_bytes()))
+        .end_text();
+}
+
+/// Draw a maze with its top left corner at (x, y), in pdf coordinates
+/// where y grows upwards.
+fn draw_maze(
+    content: &mut Content,
+    maze: &WilsonMaze,
+    solution: Option<&HashSet<(usize, usize)>>,
+    (x, y): (f32, f32),
+    cell: f32,
+    options: &PdfOptions,
+) {
+    let square = |content: &mut Content, (row, col): (usize, usize), inset: f32| {
+        content.rect(
+            x + col as f32 * cell + inset,
+            y - (row + 1) as f32 * cell + inset,
+            cell - 2.0 * inset,
+            cell - 2.0 * inset,
+        );
+    };
+
+    if let Some(solution) = solution {
+        set_fill(content, &options.colours.solution);
+        for cell_pos in solution {
+            square(content, *cell_pos, cell * 0.3);
+        }
+        content.fill_nonzero();
+    }
+
+    set_fill(content, &options.colours.start);
+    square(content, maze.start_cell, cell * 0.2);
+    content.fill_nonzero();
+
+    set_fill(content, &options.colours.end);
+    for end in maze.ends() {
+        square(content, end, cell * 0.2);
+    }
+    content.fill_nonzero();
+
+    let wall = &options.colours.wall;
+    content
+        .set_stroke_rgb(
+            wall[0] as f32 / 255.0,
+            wall[1] as f32 / 255.0,
+            wall[2] as f32 / 255.0,
+        )
+        .set_line_width(options.wall_thickness)
+        .set_line_cap(pdf_writer::types::LineCapStyle::ProjectingSquareCap);
+
+    content.rect(
+        x,
+        y - maze.rows as f32 * cell,
+        maze.cols as f32 * cell,
+        maze.rows as f32 * cell,
+    );
+
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            let left = x + col as f32 * cell;
+            let top = y - row as f32 * cell;
+
+            if col + 1 < maze.cols && !maze.grid.has_passage(row, col, &Direction::Right) {
+                content.move_to(left + cell, top);
+                content.line_to(left + cell, top - cell);
+            }
+
+            if row + 1 < maze.rows && !maze.grid.has_passage(row, col, &Direction::Down) {
+                content.move_to(left, top - cell);
+                content.line_to(left + cell, top - cell);
+            }
+        }
+    }
+
+    content.stroke();
+}
+
+/// Render the mazes as a printable worksheet, `mazes_per_page` to a page.
+/// Every page has the title at the top and the seeds of its mazes in the
+/// footer. With `answer_key`, the same pages follow with the shortest path
+/// drawn in.
+pub fn render_pdf(mazes: &[WilsonMaze], options: &PdfOptions) -> Result<Vec<u8>, MazeError> {
+    if mazes.is_empty() {
+        return Err(MazeError::Export("No mazes to export.".to_string()));
+    }
+
+    if options.mazes_per_page == 0 {
+        return Err(MazeError::Export(
+            "Need at least one maze per page.".to_string(),
+        ));
+    }
+
+    let solutions = match options.answer_key {
+        true => mazes
+            .iter()
+            .map(|maze| get_bfs_solution(maze, Priority::Disabled).map(|s| Some(s.path_cells())))
+            .collect::<Result<Vec<_>, MazeError>>()?,
+        false => vec![None; mazes.len()],
+    };
+
+    // Mazes on a page are laid out in a grid that is as square as possible.
+    let per_page = options.mazes_per_page;
+    let grid_cols = (per_page as f32).sqrt().ceil() as usize;
+    let grid_rows = per_page.div_ceil(grid_cols);
+
+    let (page_width, page_height) = options.page_size;
+    let margin = options.margin;
+    let header = TITLE_SIZE * 2.0;
+    let footer = LABEL_SIZE * 2.0;
+    let slot_width = (page_width - 2.0 * margin) / grid_cols as f32;
+    let slot_height = (page_height - 2.0 * margin - header - footer) / grid_rows as f32;
+
+    let chunks: Vec<(usize, &[WilsonMaze])> = mazes.chunks(per_page).enumerate().collect();
+    let mut pages: Vec<(usize, bool)> = chunks.iter().map(|(i, _)| (*i, false)).collect();
+    if options.answer_key {
+        pages.extend(chunks.iter().map(|(i, _)| (*i, true)));
+    }
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let info_id = Ref::new(4);
+    let page_ids: Vec<Ref> = (0..pages.len())
+        .map(|i| Ref::new(5 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().cloned())
+        .count(pages.len() as i32);
+    pdf.type1_font(font_id).base_font(Name(b"Helvetica"));
+    pdf.document_info(info_id).title(TextStr(options.title));
+
+    for (page_number, (chunk, answers)) in pages.iter().enumerate() {
+        let page_id = page_ids[page_number];
+        let content_id = Ref::new(page_id.get() + 1);
+        let (_, page_mazes) = chunks[*chunk];
+        let first = chunk * per_page;
+
+        let mut content = Content::new();
+
+        set_fill(&mut content, &options.colours.wall);
+        let title = match answers {
+            true => format!("{} - answers", options.title),
+            false => options.title.to_string(),
+        };
+        show_text(
+            &mut content,
+            margin,
+            page_height - margin - TITLE_SIZE,
+            TITLE_SIZE,
+            &title,
+        );
+
+        let seeds: Vec<String> = page_mazes
+            .iter()
+            .map(|maze| match maze.seed {
+                Some(seed) => seed.to_string(),
+                None => "-".to_string(),
+            })
+            .collect();
+        show_text(
+            &mut content,
+            margin,
+            margin,
+            LABEL_SIZE,
+            &format!(
+                "{}    Page {} of {}    Seed: {}",
+                options.title,
+                page_number + 1,
+                pages.len(),
+                seeds.join(", ")
+            ),
+        );
+
+        for (i, maze) in page_mazes.iter().enumerate() {
+            let slot_x = margin + (i % grid_cols) as f32 * slot_width;
+            let slot_top = page_height - margin - header - (i / grid_cols) as f32 * slot_height;
+
+            // Leave room for the label and some space between the mazes.
+            let padding = LABEL_SIZE * 2.0;
+            let cell = f32::min(
+                (slot_width - padding) / maze.cols as f32,
+                (slot_height - 2.0 * padding) / maze.rows as f32,
+            );
+            let maze_width = maze.cols as f32 * cell;
+            let x = slot_x + (slot_width - maze_width) / 2.0;
+            let y = slot_top - padding;
+
+            set_fill(&mut content, &options.colours.wall);
+            show_text(
+                &mut content,
+                x,
+                y + LABEL_SIZE / 2.0,
+                LABEL_SIZE,
+                &format!("Maze {}", first + i + 1),
+            );
+
+            draw_maze(
+                &mut content,
+                maze,
+                solutions[first + i].as_ref().filter(|_| *answers),
+                (x, y),
+                cell,
+                options,
+            );
+        }
+
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, page_width, page_height))
+            .parent(page_tree_id)
+            .contents(content_id);
+        page.resources().fonts().pair(FONT, font_id);
+        drop(page);
+
+        pdf.stream(content_id, &content.finish());
+    }
+
+    return Ok(pdf.finish());
+}
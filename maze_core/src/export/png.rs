@@ -0,0 +1,66 @@
+use crate::export::{rasterize, Canvas, RasterOptions};
+use crate::{MazeError, WilsonMaze};
+
+/// Encode a canvas as an RGBA png.
+pub fn encode_png(canvas: &Canvas) -> Result<Vec<u8>, MazeError> {
+    let mut bytes: Vec<u8> = vec![];
+
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, canvas.width as u32, canvas.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| MazeError::Export(e.to_string()))?;
+        writer
+            .write_image_data(&canvas.pixels)
+            .map_err(|e| MazeError::Export(e.to_string()))?;
+    }
+
+    return Ok(bytes);
+}
+
+/// Render the maze as a png image.
+pub fn render_png(maze: &WilsonMaze, options: &RasterOptions) -> Result<Vec<u8>, MazeError> {
+    return encode_png(&rasterize(maze, options));
+}
+
+/// Decode a png of any colour type into an RGBA canvas.
+pub fn decode_png(bytes: &[u8]) -> Result<Canvas, MazeError> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| MazeError::Parse(e.to_string()))?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buffer)
+        .map_err(|e| MazeError::Parse(e.to_string()))?;
+    let buffer = &buffer[..info.buffer_size()];
+
+    let pixels: Vec<u8> = match info.color_type {
+        png::ColorType::Rgba => buffer.to_vec(),
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 0xff])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|p| [*p, *p, *p, 0xff]).collect(),
+        png::ColorType::Indexed => {
+            return Err(MazeError::Parse(
+                "Indexed png was not expanded.".to_string(),
+            ))
+        }
+    };
+
+    return Ok(Canvas {
+        width: info.width as usize,
+        height: info.height as usize,
+        pixels: pixels,
+    });
+}
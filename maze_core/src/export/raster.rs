@@ -0,0 +1,294 @@
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use crate::export::{Colours, Rgba};
+use crate::{distance_field, Direction, WilsonMaze};
+
+/// Options for rasterizing a maze. Sizes are in px.
+#[derive(Debug, Clone, Copy)]
+pub struct RasterOptions<'a> {
+    pub cell_size: usize,
+    pub wall_thickness: usize,
+    pub margin: usize,
+    pub colours: Colours,
+    pub solution: Option<&'a HashSet<(usize, usize)>>,
+    pub visited: Option<&'a HashSet<(usize, usize)>>,
+    /// Colour each cell by its distance from the start cell.
+    pub gradient: bool,
+}
+
+impl Default for RasterOptions<'_> {
+    fn default() -> Self {
+        return Self {
+            cell_size: 30,
+            wall_thickness: 2,
+            margin: 10,
+            colours: Colours::default(),
+            solution: None,
+            visited: None,
+            gradient: false,
+        };
+    }
+}
+
+/// An RGBA image, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize, colour: Rgba) -> Self {
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for _ in 0..width * height {
+            pixels.extend_from_slice(&colour);
+        }
+
+        return Self {
+            width: width,
+            height: height,
+            pixels: pixels,
+        };
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgba {
+        let i = (y * self.width + x) * 4;
+        return [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ];
+    }
+
+    /// Set every pixel of a rectangle to the given colour, clipped to the
+    /// canvas bounds.
+    pub fn set_rect(&mut self, x: usize, y: usize, width: usize, height: usize, colour: Rgba) {
+        for py in y..std::cmp::min(y + height, self.height) {
+            for px in x..std::cmp::min(x + width, self.width) {
+                let i = (py * self.width + px) * 4;
+                self.pixels[i..i + 4].copy_from_slice(&colour);
+            }
+        }
+    }
+
+    /// Blend a rectangle with the given colour onto the canvas, clipped
+    /// to the canvas bounds.
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, colour: Rgba) {
+        for py in y..std::cmp::min(y + height, self.height) {
+            for px in x..std::cmp::min(x + width, self.width) {
+                let blended = blend(self.pixel(px, py), colour);
+                let i = (py * self.width + px) * 4;
+                self.pixels[i..i + 4].copy_from_slice(&blended);
+            }
+        }
+    }
+}
+
+/// Colour at `t` in [0, 1] of the distance gradient, from blue near
+/// the start cell to the solution colour far away from it.
+pub fn gradient_colour(colours: &Colours, t: f32) -> Rgba {
+    let near: Rgba = [0x1f, 0x3b, 0x73, 0xff];
+    let far = colours.solution;
+    let t = t.clamp(0.0, 1.0);
+
+    let mut colour = [0xff; 4];
+    for c in 0..3 {
+        colour[c] = (near[c] as f32 + (far[c] as f32 - near[c] as f32) * t).round() as u8;
+    }
+
+    return colour;
+}
+
+/// Colour of `src` blended onto `dst` by its alpha, as `Canvas::fill_rect`
+/// draws it.
+pub fn blend(dst: Rgba, src: Rgba) -> Rgba {
+    let alpha = src[3] as u32;
+    let mut colour = dst;
+
+    for c in 0..3 {
+        colour[c] = ((src[c] as u32 * alpha + dst[c] as u32 * (255 - alpha)) / 255) as u8;
+    }
+    colour[3] = std::cmp::max(dst[3], src[3]);
+
+    return colour;
+}
+
+/// Colour of every cell before anything is drawn on it, row by row: the
+/// distance gradient if it is enabled, and otherwise the background.
+pub fn cell_backgrounds(maze: &WilsonMaze, options: &RasterOptions) -> Vec<Rgba> {
+    let background = options.colours.background;
+
+    if !options.gradient {
+        return vec![background; maze.rows * maze.cols];
+    }
+
+    let distances = distance_field(maze, maze.start_cell);
+    let max_distance = distances.iter().flatten().max().cloned().unwrap_or(0);
+
+    return distances
+        .iter()
+        .map(|distance| match distance {
+            Some(distance) => {
+                let t = *distance as f32 / std::cmp::max(max_distance, 1) as f32;
+                blend(background, gradient_colour(&options.colours, t))
+            }
+            None => background,
+        })
+        .collect();
+}
+
+/// Draw everything inside a cell: its background, the visited and
+/// solution marks and the start and end markers. Walls are drawn
+/// separately, see `paint_walls`.
+fn paint_cell(
+    canvas: &mut Canvas,
+    maze: &WilsonMaze,
+    options: &RasterOptions,
+    background: Rgba,
+    cell_pos: (usize, usize),
+) {
+    let cell = options.cell_size;
+    let (x, y) = (
+        options.margin + cell_pos.1 * cell,
+        options.margin + cell_pos.0 * cell,
+    );
+    let inset = |canvas: &mut Canvas, inset: usize, colour: Rgba| {
+        canvas.fill_rect(
+            x + inset,
+            y + inset,
+            cell - 2 * inset,
+            cell - 2 * inset,
+            colour,
+        );
+    };
+
+    canvas.set_rect(x, y, cell, cell, background);
+
+    let is_solution = options.solution.is_some_and(|s| s.contains(&cell_pos));
+    let is_visited = options.visited.is_some_and(|v| v.contains(&cell_pos));
+
+    if is_visited && !is_solution {
+        inset(canvas, 0, options.colours.visited);
+    }
+    if is_solution {
+        inset(canvas, cell * 3 / 10, options.colours.solution);
+    }
+    if cell_pos == maze.start_cell {
+        inset(canvas, cell / 5, options.colours.start);
+    }
+    if maze.is_end(cell_pos) {
+        inset(canvas, cell / 5, options.colours.end);
+    }
+}
+
+/// Draw the walls on the grid lines `rows` and `cols`, and along the cells
+/// between them. Walls are centered on the grid lines, and extended by half
+/// their thickness at both ends so that corners are filled.
+fn paint_walls(
+    canvas: &mut Canvas,
+    maze: &WilsonMaze,
+    options: &RasterOptions,
+    rows: RangeInclusive<usize>,
+    cols: RangeInclusive<usize>,
+) {
+    let cell = options.cell_size;
+    let thickness = options.wall_thickness;
+    let half = thickness / 2;
+    let wall = options.colours.wall;
+    let origin = |(row, col): (usize, usize)| {
+        (
+            (options.margin + col * cell).saturating_sub(half),
+            (options.margin + row * cell).saturating_sub(half),
+        )
+    };
+
+    for row in *rows.start()..=std::cmp::min(*rows.end(), maze.rows) {
+        for col in *cols.start()..std::cmp::min(*cols.end() + 1, maze.cols) {
+            let is_border = row == 0 || row == maze.rows;
+            if is_border || !maze.grid.has_passage(row - 1, col, &Direction::Down) {
+                let (x, y) = origin((row, col));
+                canvas.fill_rect(x, y, cell + thickness, thickness, wall);
+            }
+        }
+    }
+
+    for row in *rows.start()..std::cmp::min(*rows.end() + 1, maze.rows) {
+        for col in *cols.start()..=std::cmp::min(*cols.end(), maze.cols) {
+            let is_border = col == 0 || col == maze.cols;
+            if is_border || !maze.grid.has_passage(row, col - 1, &Direction::Right) {
+                let (x, y) = origin((row, col));
+                canvas.fill_rect(x, y, thickness, cell + thickness, wall);
+            }
+        }
+    }
+}
+
+/// Width and height in px of the image `rasterize` draws, saturating
+/// instead of overflowing so that callers can reject huge sizes first.
+pub fn image_size(maze: &WilsonMaze, options: &RasterOptions) -> (usize, usize) {
+    let margins = options.margin.saturating_mul(2);
+    return (
+        maze.cols
+            .saturating_mul(options.cell_size)
+            .saturating_add(margins),
+        maze.rows
+            .saturating_mul(options.cell_size)
+            .saturating_add(margins),
+    );
+}
+
+/// Draw the maze onto a new canvas.
+pub fn rasterize(maze: &WilsonMaze, options: &RasterOptions) -> Canvas {
+    let (width, height) = image_size(maze, options);
+    let mut canvas = Canvas::new(width, height, options.colours.background);
+
+    let backgrounds = cell_backgrounds(maze, options);
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            paint_cell(
+                &mut canvas,
+                maze,
+                options,
+                backgrounds[row * maze.cols + col],
+                (row, col),
+            );
+        }
+    }
+
+    paint_walls(&mut canvas, maze, options, 0..=maze.rows, 0..=maze.cols);
+
+    return canvas;
+}
+
+/// Draw a cell of a canvas from `rasterize` again, e.g. after the visited
+/// cells in `options` changed. Only the cell and the walls around it are
+/// drawn, so this is much cheaper than rasterizing the whole maze.
+/// `backgrounds` are the ones from `cell_backgrounds`.
+pub fn repaint_cell(
+    canvas: &mut Canvas,
+    maze: &WilsonMaze,
+    options: &RasterOptions,
+    backgrounds: &[Rgba],
+    cell_pos: (usize, usize),
+) {
+    let (row, col) = cell_pos;
+
+    paint_cell(
+        canvas,
+        maze,
+        options,
+        backgrounds[row * maze.cols + col],
+        cell_pos,
+    );
+    // The walls of the neighbours reach into the corners of the cell.
+    paint_walls(
+        canvas,
+        maze,
+        options,
+        row.saturating_sub(1)..=row + 1,
+        col.saturating_sub(1)..=col + 1,
+    );
+}
@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::export::{Colours, Rgba};
+use crate::{Direction, WilsonMaze};
+
+/// Options for `render_svg`. Sizes are in px.
+#[derive(Debug, Clone, Copy)]
+pub struct SvgOptions<'a> {
+    pub cell_size: f32,
+    pub wall_thickness: f32,
+    pub margin: f32,
+    pub colours: Colours,
+    pub solution: Option<&'a HashSet<(usize, usize)>>,
+    pub visited: Option<&'a HashSet<(usize, usize)>>,
+}
+
+impl Default for SvgOptions<'_> {
+    fn default() -> Self {
+        return Self {
+            cell_size: 30.0,
+            wall_thickness: 2.0,
+            margin: 10.0,
+            colours: Colours::default(),
+            solution: None,
+            visited: None,
+        };
+    }
+}
+
+/// Colour as an svg fill/stroke value and its opacity.
+fn svg_colour(colour: &Rgba) -> (String, f32) {
+    return (
+        format!("#{:02x}{:02x}{:02x}", colour[0], colour[1], colour[2]),
+        colour[3] as f32 / 255.0,
+    );
+}
+
+/// Path of squares covering the given cells, inset by `inset` on each side.
+fn cells_path<'a>(
+    cells: impl Iterator<Item = &'a (usize, usize)>,
+    cell_size: f32,
+    inset: f32,
+) -> String {
+    let mut d = String::new();
+    let side = cell_size - 2.0 * inset;
+
+    for (row, col) in cells {
+        let x = *col as f32 * cell_size + inset;
+        let y = *row as f32 * cell_size + inset;
+        write!(d, "M{x} {y}h{side}v{side}h-{side}z").unwrap();
+    }
+
+    return d;
+}
+
+/// Path of all walls, including the outer ones.
+fn walls_path(maze: &WilsonMaze, cell_size: f32) -> String {
+    let mut d = String::new();
+    let width = maze.cols as f32 * cell_size;
+    let height = maze.rows as f32 * cell_size;
+
+    write!(d, "M0 0h{width}v{height}h-{width}z").unwrap();
+
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            let x = col as f32 * cell_size;
+            let y = row as f32 * cell_size;
+
+            if col + 1 < maze.cols && !maze.grid.has_passage(row, col, &Direction::Right) {
+                write!(d, "M{} {y}v{cell_size}", x + cell_size).unwrap();
+            }
+
+            if row + 1 < maze.rows && !maze.grid.has_passage(row, col, &Direction::Down) {
+                write!(d, "M{x} {}h{cell_size}", y + cell_size).unwrap();
+            }
+        }
+    }
+
+    return d;
+}
+
+/// Render the maze as a standalone svg document, with the solution and
+/// visited cells (if given) and markers for the start and end cells.
+pub fn render_svg(maze: &WilsonMaze, options: &SvgOptions) -> String {
+    let cell = options.cell_size;
+    let width = maze.cols as f32 * cell + 2.0 * options.margin;
+    let height = maze.rows as f32 * cell + 2.0 * options.margin;
+
+    let (background, background_opacity) = svg_colour(&options.colours.background);
+    let (wall, wall_opacity) = svg_colour(&options.colours.wall);
+    let (solution, solution_opacity) = svg_colour(&options.colours.solution);
+    let (visited, visited_opacity) = svg_colour(&options.colours.visited);
+    let (start, start_opacity) = svg_colour(&options.colours.start);
+    let (end, end_opacity) = svg_colour(&options.colours.end);
+
+    let empty = HashSet::new();
+    let solution_cells = options.solution.unwrap_or(&empty);
+    let visited_cells = options.visited.unwrap_or(&empty);
+
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<rect width="{width}" height="{height}" fill="{background}" fill-opacity="{background_opacity}"/>"#
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<g transform="translate({} {})">"#,
+        options.margin, options.margin
+    )
+    .unwrap();
+
+    // Visited should not be drawn on top of the solution.
+    let visited_d = cells_path(
+        visited_cells
+            .iter()
+            .filter(|cell| !solution_cells.contains(cell)),
+        cell,
+        0.0,
+    );
+    writeln!(
+        svg,
+        r#"<path d="{visited_d}" fill="{visited}" fill-opacity="{visited_opacity}"/>"#
+    )
+    .unwrap();
+
+    let solution_d = cells_path(solution_cells.iter(), cell, cell * 0.3);
+    writeln!(
+        svg,
+        r#"<path d="{solution_d}" fill="{solution}" fill-opacity="{solution_opacity}"/>"#
+    )
+    .unwrap();
+
+    let start_d = cells_path([maze.start_cell].iter(), cell, cell * 0.2);
+    writeln!(
+        svg,
+        r#"<path d="{start_d}" fill="{start}" fill-opacity="{start_opacity}"/>"#
+    )
+    .unwrap();
+
+    let end_d = cells_path(maze.ends().iter(), cell, cell * 0.2);
+    writeln!(
+        svg,
+        r#"<path d="{end_d}" fill="{end}" fill-opacity="{end_opacity}"/>"#
+    )
+    .unwrap();
+
+    writeln!(
+        svg,
+        r#"<path d="{}" fill="none" stroke="{wall}" stroke-opacity="{wall_opacity}" stroke-width="{}" stroke-linecap="square"/>"#,
+        walls_path(maze, cell),
+        options.wall_thickness
+    )
+    .unwrap();
+
+    svg.push_str("</g>\n</svg>\n");
+
+    return svg;
+}
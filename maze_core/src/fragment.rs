@@ -0,0 +1,276 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use crate::{Direction, Generator, MazeError, WilsonMaze, SOLVERS, TERRAINS};
+
+/// Version of the url fragment written by `to_fragment`. Version 2 added
+/// terrain and version 3 exits, older fragments can still be read.
+pub const FRAGMENT_VERSION: u32 = 3;
+
+/// Largest maze that is read from a fragment, so that a link cannot make
+/// us allocate an arbitrary amount of memory.
+pub const MAX_FRAGMENT_CELLS: usize = 1_000_000;
+
+/// A maze and the solver that was selected, as read from a url fragment.
+#[derive(Debug, Clone)]
+pub struct SharedMaze {
+    pub maze: WilsonMaze,
+    pub solver: Option<String>,
+}
+
+/// Two bits per cell, row by row: whether it has a passage to the right
+/// and whether it has one down.
+fn encode_passages(maze: &WilsonMaze) -> String {
+    let mut bytes = vec![0u8; (2 * maze.rows * maze.cols).div_ceil(8)];
+
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            let bit = 2 * (row * maze.cols + col);
+            if maze.grid.has_passage(row, col, &Direction::Right) {
+                bytes[bit / 8] |= 1 << (bit % 8);
+            }
+            if maze.grid.has_passage(row, col, &Direction::Down) {
+                bytes[(bit + 1) / 8] |= 1 << ((bit + 1) % 8);
+            }
+        }
+    }
+
+    return URL_SAFE_NO_PAD.encode(bytes);
+}
+
+fn decode_passages(maze: &mut WilsonMaze, text: &str) -> Result<(), MazeError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(text)
+        .map_err(|e| MazeError::Parse(format!("Invalid passages: {}", e)))?;
+
+    if bytes.len() != (2 * maze.rows * maze.cols).div_ceil(8) {
+        return Err(MazeError::Parse(format!(
+            "Expected passages for {}x{} cells, got {} bytes.",
+            maze.rows,
+            maze.cols,
+            bytes.len()
+        )));
+    }
+
+    let is_set = |bit: usize| bytes[bit / 8] & (1 << (bit % 8)) != 0;
+
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            let bit = 2 * (row * maze.cols + col);
+
+            for (direction, set) in [
+                (Direction::Right, is_set(bit)),
+                (Direction::Down, is_set(bit + 1)),
+            ] {
+                if !set {
+                    continue;
+                }
+                if maze.grid.neighbor(row, col, &direction).is_none() {
+                    return Err(MazeError::Parse(format!(
+                        "Cell ({}, {}) has a passage through the outer wall.",
+                        row, col
+                    )));
+                }
+                maze.grid.carve(row, col, &direction);
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+/// Two bits per cell, row by row: the index of its terrain in `TERRAINS`.
+fn encode_terrain(maze: &WilsonMaze) -> String {
+    let mut bytes = vec![0u8; (2 * maze.rows * maze.cols).div_ceil(8)];
+
+    for (i, terrain) in maze.terrain.iter().enumerate() {
+        let value = TERRAINS.iter().position(|t| t == terrain).unwrap() as u8;
+        bytes[2 * i / 8] |= value << (2 * i % 8);
+    }
+
+    return URL_SAFE_NO_PAD.encode(bytes);
+}
+
+fn decode_terrain(maze: &mut WilsonMaze, text: &str) -> Result<(), MazeError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(text)
+        .map_err(|e| MazeError::Parse(format!("Invalid terrain: {}", e)))?;
+
+    if bytes.len() != (2 * maze.rows * maze.cols).div_ceil(8) {
+        return Err(MazeError::Parse(format!(
+            "Expected terrain for {}x{} cells, got {} bytes.",
+            maze.rows,
+            maze.cols,
+            bytes.len()
+        )));
+    }
+
+    for i in 0..maze.rows * maze.cols {
+        let value = (bytes[2 * i / 8] >> (2 * i % 8)) & 0b11;
+        maze.terrain[i] = *TERRAINS
+            .get(value as usize)
+            .ok_or(MazeError::Parse(format!("Invalid terrain {}.", value)))?;
+    }
+
+    return Ok(());
+}
+
+fn parse_pair(key: &str, value: &str, separator: char) -> Result<(usize, usize), MazeError> {
+    let invalid = || MazeError::Parse(format!("Invalid value '{}' for '{}'.", value, key));
+
+    let (a, b) = value.split_once(separator).ok_or_else(invalid)?;
+    let a = a.parse::<usize>().map_err(|_| invalid())?;
+    let b = b.parse::<usize>().map_err(|_| invalid())?;
+
+    return Ok((a, b));
+}
+
+/// Encode the maze and the selected solver for the fragment of a url, e.g.
+/// `v=3&size=10x10&start=0,0&end=9,9&gen=wilson&seed=42&solver=bfs`.
+///
+/// Mazes that can be generated again from their seed only store the seed.
+/// All others, e.g. ones loaded from a file, store their passages instead,
+/// as two bits per cell in url safe base64 (`grid=...`). Solvers that are
+/// not in `SOLVERS` are left out. Mazes with terrain also store it, in the
+/// same way (`terrain=...`), and mazes with several exits list the others
+/// besides the end cell (`exits=0,1;1,0`).
+pub fn to_fragment(maze: &WilsonMaze, solver: Option<&str>) -> String {
+    let mut fragment = format!(
+        "v={}&size={}x{}&start={},{}&end={},{}",
+        FRAGMENT_VERSION,
+        maze.rows,
+        maze.cols,
+        maze.start_cell.0,
+        maze.start_cell.1,
+        maze.end_cell.0,
+        maze.end_cell.1
+    );
+
+    if !maze.exits.is_empty() {
+        let exits: Vec<String> = maze
+            .exits
+            .iter()
+            .map(|(row, col)| format!("{},{}", row, col))
+            .collect();
+        fragment.push_str(&format!("&exits={}", exits.join(";")));
+    }
+
+    match (maze.generator, maze.seed) {
+        (Some(generator), Some(seed)) => {
+            fragment.push_str(&format!("&gen={}&seed={}", generator.name(), seed));
+        }
+        _ => fragment.push_str(&format!("&grid={}", encode_passages(maze))),
+    }
+
+    if maze.has_terrain() {
+        fragment.push_str(&format!("&terrain={}", encode_terrain(maze)));
+    }
+
+    if let Some(solver) = solver.filter(|s| SOLVERS.iter().any(|(name, _)| name == s)) {
+        fragment.push_str(&format!("&solver={}", solver));
+    }
+
+    return fragment;
+}
+
+/// Restore a maze from a fragment written by `to_fragment`. A leading `#`
+/// is ignored.
+pub fn from_fragment(fragment: &str) -> Result<SharedMaze, MazeError> {
+    let fragment = fragment.trim().trim_start_matches('#');
+
+    let mut version = None;
+    let mut size = None;
+    let mut start_cell = None;
+    let mut end_cell = None;
+    let mut exits = vec![];
+    let mut generator = None;
+    let mut seed = None;
+    let mut grid = None;
+    let mut terrain = None;
+    let mut solver = None;
+
+    for pair in fragment.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+        match key {
+            "v" => {
+                version = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| MazeError::Parse(format!("Invalid version '{}'.", value)))?,
+                )
+            }
+            "size" => size = Some(parse_pair(key, value, 'x')?),
+            "start" => start_cell = Some(parse_pair(key, value, ',')?),
+            "end" => end_cell = Some(parse_pair(key, value, ',')?),
+            "exits" => {
+                exits = value
+                    .split(';')
+                    .map(|exit| parse_pair(key, exit, ','))
+                    .collect::<Result<_, _>>()?
+            }
+            "gen" => generator = Some(Generator::from_name(value)?),
+            "seed" => {
+                seed = Some(
+                    value
+                        .parse::<u64>()
+                        .map_err(|_| MazeError::Parse(format!("Invalid seed '{}'.", value)))?,
+                )
+            }
+            "grid" => grid = Some(value),
+            "terrain" => terrain = Some(value),
+            "solver" => {
+                if !SOLVERS.iter().any(|(name, _)| *name == value) {
+                    return Err(MazeError::InvalidSolver(value.to_string()));
+                }
+                solver = Some(value.to_string());
+            }
+            // Unknown keys are ignored, so that other parameters can share
+            // the fragment.
+            _ => {}
+        }
+    }
+
+    if !version.is_some_and(|v| (1..=FRAGMENT_VERSION).contains(&v)) {
+        return Err(MazeError::Parse(format!(
+            "Unsupported fragment version {:?}, expected at most {}.",
+            version, FRAGMENT_VERSION
+        )));
+    }
+
+    let (rows, cols) = size.ok_or(MazeError::Parse("Missing size.".to_string()))?;
+    if rows == 0 || cols == 0 || rows.saturating_mul(cols) > MAX_FRAGMENT_CELLS {
+        return Err(MazeError::Parse(format!(
+            "Unsupported size {}x{}, at most {} cells.",
+            rows, cols, MAX_FRAGMENT_CELLS
+        )));
+    }
+
+    let start_cell = start_cell.unwrap_or((0, 0));
+    let end_cell = end_cell.unwrap_or((rows - 1, cols - 1));
+
+    // A link with cells outside of the maze is broken, e.g. a start cell
+    // that cannot be reached would make generating it loop forever.
+    let mut maze = WilsonMaze::try_new(rows, cols, start_cell, end_cell)?;
+    maze.exits = exits;
+    maze.check_ends()?;
+
+    match (grid, generator, seed) {
+        (Some(grid), _, _) => decode_passages(&mut maze, grid)?,
+        (None, Some(generator), Some(seed)) => maze.generate_seeded(generator, seed)?,
+        _ => {
+            return Err(MazeError::Parse(
+                "Expected either a grid or a generator and seed.".to_string(),
+            ))
+        }
+    }
+
+    if let Some(terrain) = terrain {
+        decode_terrain(&mut maze, terrain)?;
+    }
+
+    return Ok(SharedMaze {
+        maze: maze,
+        solver: solver,
+    });
+}
@@ -0,0 +1,118 @@
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{Direction, MazeError, WilsonMaze};
+
+/// Maze generation algorithms.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Generator {
+    /// Wilson's algorithm, which yields uniformly random perfect mazes.
+    Wilson,
+    /// Randomized depth first search, which yields long winding corridors.
+    Backtracker,
+    /// Wilson's algorithm without any dead ends, so the maze has loops and
+    /// more than one way from start to end.
+    Braid,
+}
+
+/// All available generators, as (name, label) pairs.
+pub const GENERATORS: [(&str, &str); 3] = [
+    ("wilson", "Wilson"),
+    ("backtracker", "Backtracker"),
+    ("braid", "Braid"),
+];
+
+impl Generator {
+    pub fn from_name(name: &str) -> Result<Self, MazeError> {
+        match name {
+            "wilson" => return Ok(Generator::Wilson),
+            "backtracker" => return Ok(Generator::Backtracker),
+            "braid" => return Ok(Generator::Braid),
+            _ => return Err(MazeError::InvalidGenerator(name.to_string())),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Generator::Wilson => return "wilson",
+            Generator::Backtracker => return "backtracker",
+            Generator::Braid => return "braid",
+        }
+    }
+}
+
+/// Carve a perfect maze with an iterative randomized depth first search,
+/// starting from the start cell of the maze.
+pub fn backtracker<R: Rng>(maze: &mut WilsonMaze, rng: &mut R) -> Result<(), MazeError> {
+    let mut stack: Vec<(usize, usize)> = vec![maze.start_cell];
+    maze.visited.insert(maze.start_cell);
+
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<_> = maze
+            .neighbors(cell.0, cell.1)?
+            .into_iter()
+            .filter(|(_, neighbor)| !maze.visited.contains(neighbor))
+            .collect();
+
+        match unvisited.choose(rng) {
+            Some(&(direction, next_cell)) => {
+                maze.grid.carve(cell.0, cell.1, &direction);
+                maze.visited.insert(next_cell);
+                stack.push(next_cell);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    return Ok(());
+}
+
+/// Remove a `fraction` of the dead ends of a maze by carving a passage from
+/// each to another neighbor, preferring neighbors that are dead ends too so
+/// that one passage removes two of them. Every passage added makes a loop.
+pub fn braid<R: Rng>(maze: &mut WilsonMaze, fraction: f64, rng: &mut R) -> Result<(), MazeError> {
+    let is_dead_end = |maze: &WilsonMaze, (row, col): (usize, usize)| {
+        return maze.grid.passages(row, col).count() == 1;
+    };
+
+    let mut dead_ends: Vec<(usize, usize)> = vec![];
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            if is_dead_end(maze, (row, col)) {
+                dead_ends.push((row, col));
+            }
+        }
+    }
+    dead_ends.shuffle(rng);
+
+    for cell in dead_ends {
+        // An earlier passage may have removed this dead end already.
+        if !is_dead_end(maze, cell) || !rng.random_bool(fraction.clamp(0.0, 1.0)) {
+            continue;
+        }
+
+        let closed: Vec<(Direction, (usize, usize))> = maze
+            .neighbors(cell.0, cell.1)?
+            .into_iter()
+            .filter(|(direction, _)| !maze.grid.has_passage(cell.0, cell.1, direction))
+            .collect();
+        let dead_end_neighbors: Vec<(Direction, (usize, usize))> = closed
+            .iter()
+            .filter(|(_, neighbor)| is_dead_end(maze, *neighbor))
+            .cloned()
+            .collect();
+
+        let choice = match dead_end_neighbors.is_empty() {
+            true => closed.choose(rng),
+            false => dead_end_neighbors.choose(rng),
+        };
+        if let Some((direction, _)) = choice {
+            maze.grid.carve(cell.0, cell.1, direction);
+        }
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,91 @@
+use crate::{direction_reverse, Direction};
+
+/// Compact grid representation. Each cell is stored as a bitmask of
+/// the directions in which it has a passage to its neighbor, in a flat
+/// vector indexed as row * cols + col.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    pub rows: usize,
+    pub cols: usize,
+    cells: Vec<u8>,
+}
+
+impl Direction {
+    #[inline]
+    pub fn bit(&self) -> u8 {
+        match self {
+            Direction::Up => 0b0001,
+            Direction::Down => 0b0010,
+            Direction::Left => 0b0100,
+            Direction::Right => 0b1000,
+        }
+    }
+}
+
+impl Grid {
+    /// A grid where no cells are connected.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        return Self {
+            rows: rows,
+            cols: cols,
+            cells: vec![0; rows * cols],
+        };
+    }
+
+    #[inline]
+    fn index(&self, row: usize, col: usize) -> usize {
+        debug_assert!(row < self.rows && col < self.cols);
+        return row * self.cols + col;
+    }
+
+    #[inline]
+    pub fn has_passage(&self, row: usize, col: usize, direction: &Direction) -> bool {
+        return self.cells[self.index(row, col)] & direction.bit() != 0;
+    }
+
+    /// The cell next to (row, col) in the given direction, if within the grid.
+    #[inline]
+    pub fn neighbor(
+        &self,
+        row: usize,
+        col: usize,
+        direction: &Direction,
+    ) -> Option<(usize, usize)> {
+        match direction {
+            Direction::Up if row > 0 => return Some((row - 1, col)),
+            Direction::Down if row + 1 < self.rows => return Some((row + 1, col)),
+            Direction::Left if col > 0 => return Some((row, col - 1)),
+            Direction::Right if col + 1 < self.cols => return Some((row, col + 1)),
+            _ => return None,
+        }
+    }
+
+    /// Open the wall between a cell and its neighbor in the given direction.
+    /// Both cells are updated, so that passages are always symmetric.
+    pub fn carve(&mut self, row: usize, col: usize, direction: &Direction) {
+        let (nrow, ncol) = self
+            .neighbor(row, col, direction)
+            .expect("Cannot carve through the outer wall.");
+
+        let i = self.index(row, col);
+        let j = self.index(nrow, ncol);
+
+        self.cells[i] |= direction.bit();
+        self.cells[j] |= direction_reverse(direction).bit();
+    }
+
+    /// All directions in which the cell has a passage.
+    pub fn passages(&self, row: usize, col: usize) -> impl Iterator<Item = Direction> {
+        let bits = self.cells[self.index(row, col)];
+
+        return Direction::as_list()
+            .into_iter()
+            .filter(move |direction| bits & direction.bit() != 0);
+    }
+
+    /// True if the cell is not connected to any of its neighbors.
+    #[inline]
+    pub fn is_closed(&self, row: usize, col: usize) -> bool {
+        return self.cells[self.index(row, col)] == 0;
+    }
+}
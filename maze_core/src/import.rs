@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use crate::export::{decode_png, Canvas, Colours, Rgba};
+use crate::{Direction, MazeError, WilsonMaze};
+
+/// Options for `import_image`.
+#[derive(Debug, Clone, Copy)]
+pub struct ImportOptions {
+    /// Colours of the start and end markers, if the image has any.
+    pub colours: Colours,
+    /// How much darker or lighter than the background a pixel has to be to
+    /// count as a wall.
+    pub threshold: u8,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        return Self {
+            colours: Colours::default(),
+            threshold: 64,
+        };
+    }
+}
+
+/// Luminance of a pixel drawn on white.
+fn luminance(pixel: &[u8]) -> u8 {
+    let lum = (299 * pixel[0] as u32 + 587 * pixel[1] as u32 + 114 * pixel[2] as u32) / 1000;
+    let alpha = pixel[3] as u32;
+    return ((lum * alpha + 255 * (255 - alpha)) / 255) as u8;
+}
+
+/// The most common value, preferring the smallest one on ties.
+fn most_common(values: impl Iterator<Item = usize>) -> Option<usize> {
+    let mut counts: HashMap<usize, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+
+    return counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(value, _)| value);
+}
+
+/// Lengths of the runs of walls and of the runs of background pixels that
+/// lie between two walls.
+fn run_lengths(line: impl Iterator<Item = bool>, walls: &mut Vec<usize>, gaps: &mut Vec<usize>) {
+    let mut current: Option<(bool, usize)> = None;
+    let mut seen_wall = false;
+
+    for is_wall in line {
+        match current {
+            Some((wall, len)) if wall == is_wall => current = Some((wall, len + 1)),
+            Some((wall, len)) => {
+                match wall {
+                    true => walls.push(len),
+                    false if seen_wall => gaps.push(len),
+                    false => {}
+                }
+                seen_wall |= wall;
+                current = Some((is_wall, 1));
+            }
+            None => current = Some((is_wall, 1)),
+        }
+    }
+
+    if let Some((true, len)) = current {
+        walls.push(len);
+    }
+}
+
+fn close_to(pixel: &[u8], colour: &Rgba) -> bool {
+    let diff: u32 = (0..3)
+        .map(|i| (pixel[i] as i32 - colour[i] as i32).unsigned_abs())
+        .sum();
+    return diff < 48;
+}
+
+/// Build a maze from an image of one, with walls that stand out from a
+/// plain background. The wall thickness and cell size are the most common
+/// lengths of walls and gaps along the rows and columns of pixels.
+///
+/// The start and end cells are found from markers in the colours of
+/// `options.colours`, and otherwise from openings in the outer wall. If
+/// neither is found, the top left and bottom right cells are used.
+pub fn import_image(canvas: &Canvas, options: &ImportOptions) -> Result<WilsonMaze, MazeError> {
+    let (width, height) = (canvas.width, canvas.height);
+    let lum: Vec<u8> = canvas.pixels.chunks_exact(4).map(luminance).collect();
+
+    let background = most_common(lum.iter().map(|l| *l as usize))
+        .ok_or(MazeError::Parse("Empty image.".to_string()))?;
+    let walls: Vec<bool> = lum
+        .iter()
+        .map(|l| (*l as i32 - background as i32).unsigned_abs() > options.threshold as u32)
+        .collect();
+    let is_wall = |x: usize, y: usize| walls[y * width + x];
+
+    // The outer wall is the bounding box of all walls.
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+    for y in 0..height {
+        for x in 0..width {
+            if is_wall(x, y) {
+                bounds = Some(match bounds {
+                    Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                    None => (x, y, x, y),
+                });
+            }
+        }
+    }
+    let (x0, y0, x1, y1) = bounds.ok_or(MazeError::Parse("No walls in image.".to_string()))?;
+
+    let mut wall_runs: Vec<usize> = vec![];
+    let mut gap_runs: Vec<usize> = vec![];
+    for y in y0..=y1 {
+        run_lengths(
+            (x0..=x1).map(|x| is_wall(x, y)),
+            &mut wall_runs,
+            &mut gap_runs,
+        );
+    }
+    for x in x0..=x1 {
+        run_lengths(
+            (y0..=y1).map(|y| is_wall(x, y)),
+            &mut wall_runs,
+            &mut gap_runs,
+        );
+    }
+
+    let thickness = most_common(wall_runs.into_iter()).unwrap_or(1);
+    let cell_size = most_common(gap_runs.into_iter())
+        .ok_or(MazeError::Parse("No cells in image.".to_string()))?
+        + thickness;
+
+    let inner_width = (x1 - x0 + 1).saturating_sub(thickness) as f32;
+    let inner_height = (y1 - y0 + 1).saturating_sub(thickness) as f32;
+    let cols = (inner_width / cell_size as f32).round() as usize;
+    let rows = (inner_height / cell_size as f32).round() as usize;
+
+    if rows == 0 || cols == 0 {
+        return Err(MazeError::Parse(format!(
+            "Could not find any cells, walls are {} px and cells {} px.",
+            thickness, cell_size
+        )));
+    }
+
+    // Grid lines run through the middle of the walls.
+    let cell_width = inner_width / cols as f32;
+    let cell_height = inner_height / rows as f32;
+    let line_x = |col: f32| x0 as f32 + thickness as f32 / 2.0 + col * cell_width;
+    let line_y = |row: f32| y0 as f32 + thickness as f32 / 2.0 + row * cell_height;
+    let pixel_at = |x: f32, y: f32| ((x as usize).min(width - 1), (y as usize).min(height - 1));
+
+    // Sample the middle of a wall on a grid line, away from the posts in
+    // the corners.
+    let vertical_wall = |line: usize, row: usize| {
+        let dark = (2..=6)
+            .map(|i| pixel_at(line_x(line as f32), line_y(row as f32 + 0.1 * i as f32)))
+            .filter(|(x, y)| is_wall(*x, *y))
+            .count();
+        return dark > 2;
+    };
+    let horizontal_wall = |line: usize, col: usize| {
+        let dark = (2..=6)
+            .map(|i| pixel_at(line_x(col as f32 + 0.1 * i as f32), line_y(line as f32)))
+            .filter(|(x, y)| is_wall(*x, *y))
+            .count();
+        return dark > 2;
+    };
+
+    // Markers cover the middle of the cell, and the solution is drawn
+    // smaller, so only markers reach a quarter of the way in.
+    let mut start_marker = None;
+    let mut end_marker = None;
+    for row in 0..rows {
+        for col in 0..cols {
+            let (x, y) = pixel_at(line_x(col as f32 + 0.25), line_y(row as f32 + 0.25));
+            let pixel = &canvas.pixels[(y * width + x) * 4..][..4];
+
+            if start_marker.is_none() && close_to(pixel, &options.colours.start) {
+                start_marker = Some((row, col));
+            } else if end_marker.is_none() && close_to(pixel, &options.colours.end) {
+                end_marker = Some((row, col));
+            }
+        }
+    }
+
+    // Openings in the outer wall, top and left first.
+    let mut openings: Vec<(usize, usize)> = vec![];
+    openings.extend(
+        (0..cols)
+            .filter(|col| !horizontal_wall(0, *col))
+            .map(|col| (0, col)),
+    );
+    openings.extend(
+        (0..rows)
+            .filter(|row| !vertical_wall(0, *row))
+            .map(|row| (row, 0)),
+    );
+    openings.extend(
+        (0..rows)
+            .filter(|row| !vertical_wall(cols, *row))
+            .map(|row| (row, cols - 1)),
+    );
+    openings.extend(
+        (0..cols)
+            .filter(|col| !horizontal_wall(rows, *col))
+            .map(|col| (rows - 1, col)),
+    );
+
+    let start_cell = start_marker.or(openings.first().cloned()).unwrap_or((0, 0));
+    let end_cell = end_marker
+        .or(openings.get(1).and(openings.last().cloned()))
+        .unwrap_or((rows - 1, cols - 1));
+
+    let mut maze = WilsonMaze::new(rows, cols, start_cell, end_cell);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            if col + 1 < cols && !vertical_wall(col + 1, row) {
+                maze.grid.carve(row, col, &Direction::Right);
+            }
+            if row + 1 < rows && !horizontal_wall(row + 1, col) {
+                maze.grid.carve(row, col, &Direction::Down);
+            }
+        }
+    }
+
+    return Ok(maze);
+}
+
+/// Build a maze from a png image of one, see `import_image`.
+pub fn import_png(bytes: &[u8], options: &ImportOptions) -> Result<WilsonMaze, MazeError> {
+    return import_image(&decode_png(bytes)?, options);
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Direction, Generator, MazeError, Solution, Terrain, WilsonMaze};
+
+/// Version of the JSON schema written by `to_json`. Bump this whenever
+/// the schema changes in a way that older readers cannot handle. Version 2
+/// added terrain and version 3 exits, older versions can still be read.
+pub const JSON_VERSION: u32 = 3;
+
+/// Largest maze that is read from JSON, so that pasted text cannot make us
+/// allocate an arbitrary amount of memory.
+pub const MAX_JSON_CELLS: usize = 4_000_000;
+
+/// A named solver together with its result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolverRun {
+    pub solver: String,
+    #[serde(flatten)]
+    pub solution: Solution,
+}
+
+/// The JSON schema of a saved maze, e.g.
+///
+/// ```json
+/// {
+///   "version": 3,
+///   "rows": 2,
+///   "cols": 2,
+///   "start_cell": [0, 0],
+///   "end_cell": [1, 1],
+///   "generator": "wilson",
+///   "seed": 42,
+///   "passages": [["down"], ["down"], ["up", "right"], ["up", "left"]],
+///   "run": { "solver": "bfs", "path": [[0, 0], ...], "visited": [[0, 0], ...] }
+/// }
+/// ```
+///
+/// Passages are listed per cell, row by row. The path of a run is in order
+/// from the start cell to the end it reached. Mazes with terrain also list
+/// the terrain of every cell, e.g. `"terrain": ["road", "mud", "water", ...]`,
+/// and mazes with several exits list the others besides the end cell, e.g.
+/// `"exits": [[0, 1], [1, 0]]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MazeRecord {
+    pub version: u32,
+    pub rows: usize,
+    pub cols: usize,
+    pub start_cell: (usize, usize),
+    pub end_cell: (usize, usize),
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exits: Vec<(usize, usize)>,
+    pub generator: Option<Generator>,
+    pub seed: Option<u64>,
+    pub passages: Vec<Vec<Direction>>,
+    /// Empty if every cell is road.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub terrain: Vec<Terrain>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<SolverRun>,
+}
+
+impl From<WilsonMaze> for MazeRecord {
+    fn from(maze: WilsonMaze) -> Self {
+        let mut passages: Vec<Vec<Direction>> = Vec::with_capacity(maze.rows * maze.cols);
+
+        for row in 0..maze.rows {
+            for col in 0..maze.cols {
+                passages.push(maze.grid.passages(row, col).collect());
+            }
+        }
+
+        let terrain = match maze.has_terrain() {
+            true => maze.terrain.clone(),
+            false => vec![],
+        };
+
+        return Self {
+            version: JSON_VERSION,
+            rows: maze.rows,
+            cols: maze.cols,
+            start_cell: maze.start_cell,
+            end_cell: maze.end_cell,
+            exits: maze.exits.clone(),
+            generator: maze.generator,
+            seed: maze.seed,
+            passages: passages,
+            terrain: terrain,
+            run: None,
+        };
+    }
+}
+
+impl TryFrom<MazeRecord> for WilsonMaze {
+    type Error = MazeError;
+
+    fn try_from(record: MazeRecord) -> Result<Self, Self::Error> {
+        if !(1..=JSON_VERSION).contains(&record.version) {
+            return Err(MazeError::Parse(format!(
+                "Unsupported version {}, expected at most {}.",
+                record.version, JSON_VERSION
+            )));
+        }
+
+        let cells = match record.rows.checked_mul(record.cols) {
+            Some(cells) if record.rows > 0 && record.cols > 0 && cells <= MAX_JSON_CELLS => cells,
+            _ => {
+                return Err(MazeError::Parse(format!(
+                    "Unsupported size {}x{}, at most {} cells.",
+                    record.rows, record.cols, MAX_JSON_CELLS
+                )))
+            }
+        };
+
+        if record.passages.len() != cells {
+            return Err(MazeError::Parse(format!(
+                "Expected passages for {} cells, got {}.",
+                cells,
+                record.passages.len()
+            )));
+        }
+
+        let mut maze =
+            WilsonMaze::try_new(record.rows, record.cols, record.start_cell, record.end_cell)?;
+        maze.exits = record.exits;
+        maze.check_ends()?;
+
+        for (i, directions) in record.passages.iter().enumerate() {
+            let (row, col) = (i / record.cols, i % record.cols);
+
+            for direction in directions {
+                if maze.grid.neighbor(row, col, direction).is_none() {
+                    return Err(MazeError::Parse(format!(
+                        "Cell ({}, {}) has a passage {:?} through the outer wall.",
+                        row, col, direction
+                    )));
+                }
+                maze.grid.carve(row, col, direction);
+            }
+        }
+
+        if !record.terrain.is_empty() {
+            if record.terrain.len() != cells {
+                return Err(MazeError::Parse(format!(
+                    "Expected terrain for {} cells, got {}.",
+                    cells,
+                    record.terrain.len()
+                )));
+            }
+            maze.terrain = record.terrain;
+        }
+
+        maze.generator = record.generator;
+        maze.seed = record.seed;
+
+        return Ok(maze);
+    }
+}
+
+/// Serialize a maze, and optionally a solver run on it, as pretty printed JSON.
+pub fn to_json(maze: &WilsonMaze, run: Option<&SolverRun>) -> Result<String, MazeError> {
+    let mut record = MazeRecord::from(maze.clone());
+    record.run = run.cloned();
+
+    return serde_json::to_string_pretty(&record).map_err(|e| MazeError::Parse(e.to_string()));
+}
+
+/// Read a maze, and the solver run if one was saved, from JSON.
+pub fn from_json(text: &str) -> Result<(WilsonMaze, Option<SolverRun>), MazeError> {
+    let mut record: MazeRecord =
+        serde_json::from_str(text).map_err(|e| MazeError::Parse(e.to_string()))?;
+    let run = record.run.take();
+
+    return Ok((WilsonMaze::try_from(record)?, run));
+}
@@ -0,0 +1,67 @@
+//! Core maze logic: grid representation, generation and solvers.
+//! This crate has no UI dependencies, so it can be shared between the
+//! Dioxus app and other tools.
+
+mod maze;
+pub use maze::{clamp_cell, direction_reverse, Direction, Neighbor, WalkStep, WilsonMaze};
+
+mod error;
+pub use error::MazeError;
+
+mod generator;
+pub use generator::{backtracker, braid, Generator, GENERATORS};
+
+mod grid;
+pub use grid::Grid;
+
+mod bfs_solver;
+pub use bfs_solver::{
+    bfs_solve, diameter, distance_field, farthest_cell, get_bfs_solution, Parents, Priority,
+};
+
+mod dfs_solver;
+pub use dfs_solver::{backtrack, get_backtrack_solution};
+
+mod dijkstra_solver;
+pub use dijkstra_solver::get_dijkstra_solution;
+
+mod terrain;
+pub use terrain::{paint_terrain, path_cost, Terrain, TERRAINS};
+
+mod solver;
+pub use solver::{solve, Solution, SOLVERS};
+
+mod json;
+pub use json::{from_json, to_json, MazeRecord, SolverRun, JSON_VERSION, MAX_JSON_CELLS};
+
+mod text;
+pub use text::{parse_text, render_directions, render_text, ParsedText, TextOptions, TextStyle};
+
+mod analysis;
+pub use analysis::{
+    analyze, difficulty, difficulty_range, generate_with_difficulty, MazeMetrics, DIFFICULTIES,
+};
+
+mod fragment;
+pub use fragment::{from_fragment, to_fragment, SharedMaze, FRAGMENT_VERSION, MAX_FRAGMENT_CELLS};
+
+mod micromouse;
+pub use micromouse::{
+    centre_goal, parse_maz, parse_micromouse_text, render_maz, render_micromouse_text,
+};
+
+mod import;
+pub use import::{import_image, import_png, ImportOptions};
+
+mod export;
+pub use export::{
+    cell_backgrounds, encode_png, gradient_colour, rasterize, render_gif, render_pdf, render_png,
+    render_svg, repaint_cell, AnimationOptions, Canvas, Colours, PdfOptions, RasterOptions, Rgba,
+    SvgOptions, A4, THEMES,
+};
+
+mod benchmark;
+pub use benchmark::{
+    run_benchmark, runs_csv, summarize, summary_csv, summary_table, BenchmarkOptions, BenchmarkRun,
+    BenchmarkSummary, Stats,
+};
@@ -0,0 +1,297 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use crate::{backtracker, braid, Generator, Grid, MazeError, MazeRecord, Terrain};
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// We can implement this with vec::with_capacity() later on.
+    pub fn as_list() -> Vec<Direction> {
+        return vec![
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ];
+    }
+}
+
+/// A neighbouring cell and the direction it lies in.
+pub type Neighbor = (Direction, (usize, usize));
+
+/// One step of a random walk: the cell, the direction it is left in, and
+/// the cell that direction leads to.
+pub type WalkStep = ((usize, usize), Direction, (usize, usize));
+
+pub fn direction_reverse(direction: &Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Down,
+        Direction::Down => Direction::Up,
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Left,
+    }
+}
+/// Clamp a cell so that it lies within a grid of the given dimensions.
+/// Used by the app to keep start and end cells from user input within the
+/// maze.
+pub fn clamp_cell(cell: (usize, usize), rows: usize, cols: usize) -> (usize, usize) {
+    return (
+        std::cmp::min(cell.0, rows.saturating_sub(1)),
+        std::cmp::min(cell.1, cols.saturating_sub(1)),
+    );
+}
+
+// Named after the first (and default) generation algorithm,
+// see `Generator` for the supported ones.
+//
+// Serialized through `MazeRecord`, see json.rs for the schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "MazeRecord", try_from = "MazeRecord")]
+pub struct WilsonMaze {
+    pub rows: usize,
+    pub cols: usize,
+    pub start_cell: (usize, usize),
+    pub end_cell: (usize, usize),
+    /// Other exits besides the end cell, e.g. the rest of a micromouse goal
+    /// region. Solvers stop at whichever end they reach first.
+    pub exits: Vec<(usize, usize)>,
+    pub visited: HashSet<(usize, usize)>,
+    pub grid: Grid,
+    /// Generator and seed used for the grid, if it was generated.
+    pub generator: Option<Generator>,
+    pub seed: Option<u64>,
+    /// Terrain of every cell, row by row.
+    pub terrain: Vec<Terrain>,
+}
+
+impl WilsonMaze {
+    /// Maze without passages. The start and end cells are not checked, see
+    /// `try_new` for cells that come from user input.
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        start_cell: (usize, usize),
+        end_cell: (usize, usize),
+    ) -> Self {
+        return Self {
+            rows: rows,
+            cols: cols,
+            start_cell: start_cell,
+            end_cell: end_cell,
+            exits: vec![],
+            visited: HashSet::new(),
+            grid: Grid::new(rows, cols),
+            generator: None,
+            seed: None,
+            terrain: vec![Terrain::default(); rows * cols],
+        };
+    }
+
+    /// Like `new`, but fails with `MazeError::OutOfBounds` if the start or
+    /// end cell lies outside of the maze.
+    pub fn try_new(
+        rows: usize,
+        cols: usize,
+        start_cell: (usize, usize),
+        end_cell: (usize, usize),
+    ) -> Result<Self, MazeError> {
+        let maze = Self::new(rows, cols, start_cell, end_cell);
+        maze.check_ends()?;
+
+        return Ok(maze);
+    }
+
+    /// The end cell followed by the other exits, without duplicates.
+    pub fn ends(&self) -> Vec<(usize, usize)> {
+        let mut ends = vec![self.end_cell];
+        for exit in &self.exits {
+            if !ends.contains(exit) {
+                ends.push(*exit);
+            }
+        }
+
+        return ends;
+    }
+
+    pub fn is_end(&self, cell: (usize, usize)) -> bool {
+        return cell == self.end_cell || self.exits.contains(&cell);
+    }
+
+    /// Ok if the start cell and every end lie within the maze.
+    pub fn check_ends(&self) -> Result<(), MazeError> {
+        self.check_cell(self.start_cell)?;
+        for end in self.ends() {
+            self.check_cell(end)?;
+        }
+
+        return Ok(());
+    }
+
+    pub fn terrain(&self, row: usize, col: usize) -> Terrain {
+        return self.terrain[row * self.cols + col];
+    }
+
+    pub fn set_terrain(&mut self, row: usize, col: usize, terrain: Terrain) {
+        self.terrain[row * self.cols + col] = terrain;
+    }
+
+    /// Whether any cell has a terrain other than road.
+    pub fn has_terrain(&self) -> bool {
+        return self.terrain.iter().any(|t| *t != Terrain::Road);
+    }
+
+    pub fn direction_lookup(&self, direction: &Direction) -> (i32, i32) {
+        match direction {
+            Direction::Up => return (-1, 0),
+            Direction::Down => return (1, 0),
+            Direction::Left => return (0, -1),
+            Direction::Right => return (0, 1),
+        }
+    }
+
+    pub fn max_dist(&self) -> usize {
+        return self.rows + self.cols;
+    }
+
+    pub fn in_bounds(&self, row: i32, col: i32) -> bool {
+        return (row >= 0 && row < self.rows as i32) && (col >= 0 && col < self.cols as i32);
+    }
+
+    /// Ok if the cell lies within the maze.
+    pub fn check_cell(&self, cell: (usize, usize)) -> Result<(), MazeError> {
+        if cell.0 >= self.rows || cell.1 >= self.cols {
+            return Err(MazeError::OutOfBounds {
+                cell: cell,
+                rows: self.rows,
+                cols: self.cols,
+            });
+        }
+
+        return Ok(());
+    }
+
+    pub fn neighbors(&self, row: usize, col: usize) -> Result<Vec<Neighbor>, MazeError> {
+        self.check_cell((row, col))?;
+
+        // We have at most 4 neighbors.
+        let mut neigbors: Vec<Neighbor> = Vec::with_capacity(4);
+
+        for direction in Direction::as_list() {
+            let (row_offset, col_offset) = self.direction_lookup(&direction);
+
+            let neighbor_row = row_offset + row as i32;
+            let neighbor_col = col_offset + col as i32;
+
+            if self.in_bounds(neighbor_row, neighbor_col) {
+                neigbors.push((direction, (neighbor_row as usize, neighbor_col as usize)));
+            }
+        }
+
+        return Ok(neigbors);
+    }
+
+    pub fn random_walk<R: Rng>(
+        &mut self,
+        start: (usize, usize),
+        rng: &mut R,
+    ) -> Result<Vec<WalkStep>, MazeError> {
+        // Since we overwrite the exit direction of a cell every time the walk
+        // passes it, loops are implicitly erased when following the path below.
+        let mut path: HashMap<(usize, usize), (Direction, (usize, usize))> = HashMap::new();
+
+        let mut cell = start;
+
+        while !self.visited.contains(&cell) {
+            let (direction, next_cell) = *self.neighbors(cell.0, cell.1)?.choose(rng).unwrap();
+
+            path.insert(cell, (direction, next_cell));
+
+            cell = next_cell;
+        }
+
+        let mut final_path: Vec<WalkStep> = vec![];
+        cell = start;
+
+        while !self.visited.contains(&cell) {
+            let (direction, next_cell) = *path.get(&cell).unwrap();
+
+            final_path.push((cell, direction, next_cell));
+
+            cell = next_cell;
+        }
+
+        return Ok(final_path);
+    }
+
+    /// Generate a maze with Wilson's algorithm and a random seed.
+    pub fn generate(&mut self) -> Result<(), MazeError> {
+        return self.generate_seeded(Generator::Wilson, rand::random());
+    }
+
+    /// Generate a maze with the given generator. The same generator, seed
+    /// and dimensions always yield the same grid.
+    pub fn generate_seeded(&mut self, generator: Generator, seed: u64) -> Result<(), MazeError> {
+        self.check_cell(self.start_cell)?;
+
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        match generator {
+            Generator::Wilson => self.wilson(&mut rng)?,
+            Generator::Backtracker => backtracker(self, &mut rng)?,
+            Generator::Braid => {
+                self.wilson(&mut rng)?;
+                braid(self, 1.0, &mut rng)?;
+            }
+        }
+
+        self.generator = Some(generator);
+        self.seed = Some(seed);
+
+        return Ok(());
+    }
+
+    pub fn wilson<R: Rng>(&mut self, rng: &mut R) -> Result<(), MazeError> {
+        self.visited.insert(self.start_cell);
+
+        // Wilson's algorithm works for any order of starting cells, so we
+        // shuffle once instead of picking a random unvisited cell each time.
+        let mut unvisited: Vec<(usize, usize)> = Vec::with_capacity(self.rows * self.cols);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                unvisited.push((row, col));
+            }
+        }
+        unvisited.shuffle(rng);
+
+        for start in unvisited {
+            if self.visited.contains(&start) {
+                continue;
+            }
+
+            let walk = self.random_walk(start, rng)?;
+
+            for (cell, direction, next_cell) in walk {
+                self.grid.carve(cell.0, cell.1, &direction);
+
+                self.visited.insert(cell);
+                self.visited.insert(next_cell);
+            }
+        }
+
+        return Ok(());
+    }
+}
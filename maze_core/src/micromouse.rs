@@ -0,0 +1,212 @@
+//! Micromouse competition mazes, in the binary `.maz` format and the text
+//! format of the classic maze archives.
+//!
+//! Micromouse mazes start in the bottom left corner and have a goal region
+//! of 2x2 cells in the centre. The goal cell that is closest to the start
+//! becomes the end cell and the others become exits, so the solvers stop
+//! at whichever goal cell they reach first.
+
+use crate::{distance_field, parse_text, Direction, MazeError, WilsonMaze};
+
+/// Wall bits of a cell in the `.maz` format.
+const NORTH: u8 = 1;
+const EAST: u8 = 2;
+const SOUTH: u8 = 4;
+const WEST: u8 = 8;
+
+/// The 2x2 cells in the centre of the maze, or fewer along an odd side.
+pub fn centre_goal(rows: usize, cols: usize) -> Vec<(usize, usize)> {
+    let middle = |n: usize| -> Vec<usize> {
+        match n % 2 {
+            0 => return vec![n / 2 - 1, n / 2],
+            _ => return vec![n / 2],
+        }
+    };
+
+    let mut goal = vec![];
+    for row in middle(rows) {
+        for col in middle(cols) {
+            goal.push((row, col));
+        }
+    }
+
+    return goal;
+}
+
+/// Make the goal cell closest to the start the end cell, and the other
+/// goal cells exits.
+fn set_goals(maze: &mut WilsonMaze, goals: &[(usize, usize)]) {
+    let distances = distance_field(maze, maze.start_cell);
+
+    maze.end_cell = goals
+        .iter()
+        .min_by_key(|(row, col)| distances[row * maze.cols + col].unwrap_or(usize::MAX))
+        .cloned()
+        .unwrap_or((maze.rows - 1, maze.cols - 1));
+    maze.exits = goals
+        .iter()
+        .cloned()
+        .filter(|goal| *goal != maze.end_cell)
+        .collect();
+}
+
+/// Goal cells to write out: all ends if there are several, the centre if
+/// the end cell is part of it, and otherwise only the end cell.
+fn goals(maze: &WilsonMaze) -> Vec<(usize, usize)> {
+    if !maze.exits.is_empty() {
+        return maze.ends();
+    }
+
+    let centre = centre_goal(maze.rows, maze.cols);
+
+    match centre.contains(&maze.end_cell) {
+        true => return centre,
+        false => return vec![maze.end_cell],
+    }
+}
+
+/// Index of a cell in the `.maz` format, which goes column by column
+/// starting from the bottom left.
+fn maz_index(size: usize, row: usize, col: usize) -> usize {
+    return col * size + (size - 1 - row);
+}
+
+/// Read a maze in the binary `.maz` format: one byte per cell of a square
+/// maze, with the bits N=1, E=2, S=4 and W=8 set for walls. Classic mazes
+/// are 16x16 (256 bytes), half size ones 32x32.
+///
+/// A passage is only carved if the cells on both sides of it agree.
+pub fn parse_maz(bytes: &[u8]) -> Result<WilsonMaze, MazeError> {
+    let size = (bytes.len() as f64).sqrt() as usize;
+
+    if size < 2 || size * size != bytes.len() {
+        return Err(MazeError::Parse(format!(
+            "Expected a square maze, but got {} bytes.",
+            bytes.len()
+        )));
+    }
+
+    let walls = |row: usize, col: usize| bytes[maz_index(size, row, col)];
+
+    let mut maze = WilsonMaze::new(size, size, (size - 1, 0), (0, size - 1));
+
+    for row in 0..size {
+        for col in 0..size {
+            if col + 1 < size && walls(row, col) & EAST == 0 && walls(row, col + 1) & WEST == 0 {
+                maze.grid.carve(row, col, &Direction::Right);
+            }
+            if row + 1 < size && walls(row, col) & SOUTH == 0 && walls(row + 1, col) & NORTH == 0 {
+                maze.grid.carve(row, col, &Direction::Down);
+            }
+        }
+    }
+
+    set_goals(&mut maze, &centre_goal(size, size));
+
+    return Ok(maze);
+}
+
+/// Write a square maze in the binary `.maz` format, see `parse_maz`.
+pub fn render_maz(maze: &WilsonMaze) -> Result<Vec<u8>, MazeError> {
+    if maze.rows != maze.cols {
+        return Err(MazeError::Export(format!(
+            "Micromouse mazes are square, but this one is {}x{}.",
+            maze.rows, maze.cols
+        )));
+    }
+
+    let size = maze.rows;
+    let mut bytes = vec![0; size * size];
+
+    for row in 0..size {
+        for col in 0..size {
+            let mut walls = 0;
+            for (direction, bit) in [
+                (Direction::Up, NORTH),
+                (Direction::Right, EAST),
+                (Direction::Down, SOUTH),
+                (Direction::Left, WEST),
+            ] {
+                if !maze.grid.has_passage(row, col, &direction) {
+                    walls |= bit;
+                }
+            }
+            bytes[maz_index(size, row, col)] = walls;
+        }
+    }
+
+    return Ok(bytes);
+}
+
+/// Read a maze in the text format of the micromouse maze archives, with
+/// `o` posts, `---` and `|` walls, and the start and goal cells marked
+/// with `S` and `G`:
+///
+/// ```text
+/// o---o---o
+/// | G     |
+/// o   o---o
+/// | S     |
+/// o---o---o
+/// ```
+///
+/// Without markers, the start is the bottom left cell and the goal is the
+/// centre of the maze.
+pub fn parse_micromouse_text(text: &str) -> Result<WilsonMaze, MazeError> {
+    let parsed = parse_text(text)?;
+    let mut maze = parsed.maze;
+
+    if !text.contains('S') {
+        maze.start_cell = (maze.rows - 1, 0);
+    }
+
+    let goals = match parsed.goals.is_empty() {
+        true => centre_goal(maze.rows, maze.cols),
+        false => parsed.goals,
+    };
+    set_goals(&mut maze, &goals);
+
+    return Ok(maze);
+}
+
+/// Write a maze in the text format of the micromouse maze archives, see
+/// `parse_micromouse_text`. All ends are marked as goals, or the whole
+/// centre if there is only an end cell and it is part of it.
+pub fn render_micromouse_text(maze: &WilsonMaze) -> String {
+    let goals = goals(maze);
+    let mut text = String::new();
+
+    let wall_line = |text: &mut String, row: usize| {
+        for col in 0..maze.cols {
+            text.push('o');
+            let wall =
+                row == 0 || row == maze.rows || !maze.grid.has_passage(row, col, &Direction::Up);
+            text.push_str(if wall { "---" } else { "   " });
+        }
+        text.push_str("o\n");
+    };
+
+    for row in 0..maze.rows {
+        wall_line(&mut text, row);
+
+        text.push('|');
+        for col in 0..maze.cols {
+            if (row, col) == maze.start_cell {
+                text.push_str(" S ");
+            } else if goals.contains(&(row, col)) {
+                text.push_str(" G ");
+            } else {
+                text.push_str("   ");
+            }
+
+            match maze.grid.has_passage(row, col, &Direction::Right) {
+                true => text.push(' '),
+                false => text.push('|'),
+            }
+        }
+        text.push('\n');
+    }
+    wall_line(&mut text, maze.rows);
+
+    return text;
+}
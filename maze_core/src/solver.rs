@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::{
+    get_backtrack_solution, get_bfs_solution, get_dijkstra_solution, Direction, MazeError,
+    Priority, WilsonMaze,
+};
+
+/// The result of running a solver on a maze.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Solution {
+    /// Cells on the path, in order from the start cell to the end cell.
+    pub path: Vec<(usize, usize)>,
+    /// Cells in the order the solver visited them.
+    pub visited: Vec<(usize, usize)>,
+}
+
+impl Solution {
+    /// Number of steps along the path, one less than its cells.
+    pub fn steps(&self) -> usize {
+        return self.path.len().saturating_sub(1);
+    }
+
+    /// Cells on the path, for looking up whether a cell is on it.
+    pub fn path_cells(&self) -> HashSet<(usize, usize)> {
+        return self.path.iter().cloned().collect();
+    }
+
+    /// The direction of every step along the path.
+    pub fn directions(&self) -> Vec<Direction> {
+        return self
+            .path
+            .windows(2)
+            .map(|step| {
+                let ((row, col), (nrow, ncol)) = (step[0], step[1]);
+                match (nrow.cmp(&row), ncol.cmp(&col)) {
+                    (std::cmp::Ordering::Less, _) => Direction::Up,
+                    (std::cmp::Ordering::Greater, _) => Direction::Down,
+                    (_, std::cmp::Ordering::Less) => Direction::Left,
+                    _ => Direction::Right,
+                }
+            })
+            .collect();
+    }
+}
+
+/// All available solvers, as (name, label) pairs.
+pub const SOLVERS: [(&str, &str); 6] = [
+    ("bfs", "BFS Default"),
+    ("bfs-prio", "BFS Prio (heuristic)"),
+    ("bfs-close", "BFS Close (heuristic)"),
+    ("bfs-random", "BFS Random (heuristic)"),
+    ("backtrack", "DFS Backtrack"),
+    ("dijkstra", "Dijkstra (terrain cost)"),
+];
+
+/// Solve the maze with the solver of the given name.
+pub fn solve(maze: &WilsonMaze, solver: &str) -> Result<Solution, MazeError> {
+    match solver {
+        "bfs" => return get_bfs_solution(maze, Priority::Disabled),
+        "bfs-prio" => return get_bfs_solution(maze, Priority::Prio),
+        "bfs-close" => return get_bfs_solution(maze, Priority::Close),
+        "bfs-random" => return get_bfs_solution(maze, Priority::Random),
+        "backtrack" => return get_backtrack_solution(maze),
+        "dijkstra" => return get_dijkstra_solution(maze),
+        _ => return Err(MazeError::InvalidSolver(solver.to_string())),
+    }
+}
@@ -0,0 +1,75 @@
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::WilsonMaze;
+
+/// What a cell is made of, which decides how much it costs to step onto.
+#[derive(PartialEq, Eq, Hash, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Terrain {
+    #[default]
+    Road,
+    Mud,
+    Water,
+}
+
+/// All terrains, cheapest first.
+pub const TERRAINS: [Terrain; 3] = [Terrain::Road, Terrain::Mud, Terrain::Water];
+
+impl Terrain {
+    /// Cost of stepping onto a cell of this terrain.
+    pub fn cost(&self) -> usize {
+        match self {
+            Terrain::Road => return 1,
+            Terrain::Mud => return 3,
+            Terrain::Water => return 5,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Terrain::Road => return "road",
+            Terrain::Mud => return "mud",
+            Terrain::Water => return "water",
+        }
+    }
+}
+
+/// Paint `regions` round patches of mud or water onto the maze, at random
+/// places and with random sizes. Later patches are painted over earlier
+/// ones. The passages are not changed.
+pub fn paint_terrain<R: Rng>(maze: &mut WilsonMaze, regions: usize, rng: &mut R) {
+    let max_radius = std::cmp::max(std::cmp::min(maze.rows, maze.cols) / 4, 1);
+
+    for _ in 0..regions {
+        let terrain = match rng.random_bool(0.5) {
+            true => Terrain::Mud,
+            false => Terrain::Water,
+        };
+        let (centre_row, centre_col) = (
+            rng.random_range(0..maze.rows),
+            rng.random_range(0..maze.cols),
+        );
+        let radius = rng.random_range(1..=max_radius);
+
+        for row in centre_row.saturating_sub(radius)..=(centre_row + radius).min(maze.rows - 1) {
+            for col in centre_col.saturating_sub(radius)..=(centre_col + radius).min(maze.cols - 1)
+            {
+                let (dr, dc) = (row.abs_diff(centre_row), col.abs_diff(centre_col));
+                if dr * dr + dc * dc <= radius * radius {
+                    maze.set_terrain(row, col, terrain);
+                }
+            }
+        }
+    }
+}
+
+/// Total cost of walking a path: the cost of every cell stepped onto, so
+/// not counting the first one.
+pub fn path_cost(maze: &WilsonMaze, path: &[(usize, usize)]) -> usize {
+    return path
+        .iter()
+        .skip(1)
+        .map(|(row, col)| maze.terrain(*row, *col).cost())
+        .sum();
+}
@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+
+use crate::{Direction, MazeError, Solution, WilsonMaze};
+
+/// Character set used when rendering a maze as text.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TextStyle {
+    /// Classic ASCII, with `+` posts, `--` and `|` walls.
+    Ascii,
+    /// Unicode box-drawing characters.
+    Unicode,
+}
+
+/// What to draw on top of the maze, besides the start and end cells.
+#[derive(Debug, Clone, Copy)]
+pub struct TextOptions<'a> {
+    pub style: TextStyle,
+    pub solution: Option<&'a HashSet<(usize, usize)>>,
+    pub visited: Option<&'a HashSet<(usize, usize)>>,
+}
+
+impl Default for TextOptions<'_> {
+    fn default() -> Self {
+        return Self {
+            style: TextStyle::Ascii,
+            solution: None,
+            visited: None,
+        };
+    }
+}
+
+/// A maze read back from text, together with its overlays.
+#[derive(Debug, Clone)]
+pub struct ParsedText {
+    pub maze: WilsonMaze,
+    pub solution: HashSet<(usize, usize)>,
+    pub visited: HashSet<(usize, usize)>,
+    /// Cells marked with `G`, as used for the goal region of micromouse
+    /// mazes.
+    pub goals: Vec<(usize, usize)>,
+}
+
+// Width of the inside of a cell, in characters.
+const CELL_WIDTH: usize = 2;
+
+/// Box-drawing character for a post, given which of its (up, down,
+/// left, right) arms have a wall.
+fn unicode_post(up: bool, down: bool, left: bool, right: bool) -> char {
+    match (up, down, left, right) {
+        (false, false, false, false) => return ' ',
+        (true, false, false, false) => return '╵',
+        (false, true, false, false) => return '╷',
+        (false, false, true, false) => return '╴',
+        (false, false, false, true) => return '╶',
+        (true, true, false, false) => return '│',
+        (false, false, true, true) => return '─',
+        (false, true, false, true) => return '┌',
+        (false, true, true, false) => return '┐',
+        (true, false, false, true) => return '└',
+        (true, false, true, false) => return '┘',
+        (true, true, false, true) => return '├',
+        (true, true, true, false) => return '┤',
+        (false, true, true, true) => return '┬',
+        (true, false, true, true) => return '┴',
+        (true, true, true, true) => return '┼',
+    }
+}
+
+/// True if there is a wall below the cell. Outer walls always exist.
+fn wall_below(maze: &WilsonMaze, row: usize, col: usize) -> bool {
+    return row + 1 >= maze.rows || !maze.grid.has_passage(row, col, &Direction::Down);
+}
+
+/// True if there is a wall to the right of the cell. Outer walls always exist.
+fn wall_right(maze: &WilsonMaze, row: usize, col: usize) -> bool {
+    return col + 1 >= maze.cols || !maze.grid.has_passage(row, col, &Direction::Right);
+}
+
+/// Wall line with posts, above row `row` (or below the last row).
+fn wall_line(maze: &WilsonMaze, row: usize, style: TextStyle) -> String {
+    let mut line = String::new();
+
+    // A horizontal wall segment above the cell (row, col).
+    let horizontal = |col: usize| -> bool {
+        return row == 0 || row == maze.rows || wall_below(maze, row - 1, col);
+    };
+
+    // A vertical wall segment to the left of the cell (row, col).
+    let vertical = |row: usize, col: usize| -> bool {
+        return col == 0 || col == maze.cols || wall_right(maze, row, col - 1);
+    };
+
+    for col in 0..=maze.cols {
+        let post = match style {
+            TextStyle::Ascii => '+',
+            TextStyle::Unicode => unicode_post(
+                row > 0 && vertical(row - 1, col),
+                row < maze.rows && vertical(row, col),
+                col > 0 && horizontal(col - 1),
+                col < maze.cols && horizontal(col),
+            ),
+        };
+        line.push(post);
+
+        if col == maze.cols {
+            break;
+        }
+
+        let segment = match (horizontal(col), style) {
+            (false, _) => ' ',
+            (true, TextStyle::Ascii) => '-',
+            (true, TextStyle::Unicode) => '─',
+        };
+        for _ in 0..CELL_WIDTH {
+            line.push(segment);
+        }
+    }
+
+    return line;
+}
+
+/// Step by step directions along the solution, one line per straight
+/// stretch, e.g. `right 3`.
+pub fn render_directions(solution: &Solution) -> String {
+    let mut stretches: Vec<(Direction, usize)> = vec![];
+
+    for direction in solution.directions() {
+        match stretches.last_mut() {
+            Some((last, steps)) if *last == direction => *steps += 1,
+            _ => stretches.push((direction, 1)),
+        }
+    }
+
+    let mut text = String::new();
+    for (direction, steps) in stretches {
+        let name = match direction {
+            Direction::Up => "up",
+            Direction::Down => "down",
+            Direction::Left => "left",
+            Direction::Right => "right",
+        };
+        text.push_str(&format!("{} {}\n", name, steps));
+    }
+
+    return text;
+}
+
+/// Render the maze as text, e.g. in the ASCII style
+///
+/// ```text
+/// +--+--+
+/// |S    |
+/// +--+  +
+/// |E    |
+/// +--+--+
+/// ```
+///
+/// A start cell that is also an end is marked `SE`. Cells on the solution
+/// are marked with `**` (`██` in the Unicode style) and visited cells with
+/// `..` (`░░`).
+pub fn render_text(maze: &WilsonMaze, options: &TextOptions) -> String {
+    let (vertical_wall, solution_mark, visited_mark) = match options.style {
+        TextStyle::Ascii => ('|', "**", ".."),
+        TextStyle::Unicode => ('│', "██", "░░"),
+    };
+
+    let mut text = String::new();
+
+    for row in 0..maze.rows {
+        text.push_str(&wall_line(maze, row, options.style));
+        text.push('\n');
+
+        text.push(vertical_wall);
+
+        for col in 0..maze.cols {
+            if (row, col) == maze.start_cell && maze.is_end((row, col)) {
+                text.push_str("SE");
+            } else if (row, col) == maze.start_cell {
+                text.push_str("S ");
+            } else if maze.is_end((row, col)) {
+                text.push_str("E ");
+            } else if options.solution.is_some_and(|s| s.contains(&(row, col))) {
+                text.push_str(solution_mark);
+            } else if options.visited.is_some_and(|v| v.contains(&(row, col))) {
+                text.push_str(visited_mark);
+            } else {
+                text.push_str("  ");
+            }
+
+            match wall_right(maze, row, col) {
+                true => text.push(vertical_wall),
+                false => text.push(' '),
+            }
+        }
+        text.push('\n');
+    }
+
+    text.push_str(&wall_line(maze, maze.rows, options.style));
+    text.push('\n');
+
+    return text;
+}
+
+/// Read a maze in any of the formats written by `render_text`. Any non
+/// space character at a wall position counts as a wall, so the parser
+/// accepts both styles. The cell width is taken from the distance between
+/// the first two posts (`+` or `o`) of the top line, and defaults to two.
+///
+/// Cells without an `S` or `E` marker default to the top left and bottom
+/// right corner, and `SE` marks a cell that is both. The first `E` is the
+/// end cell, any others are exits. If the text contains a solution, the
+/// start cell is part of it, and so is the end or exit next to the last
+/// cell of the path, since they are always drawn as `S` and `E`. Cells
+/// marked `G` are returned as goals.
+pub fn parse_text(text: &str) -> Result<ParsedText, MazeError> {
+    let lines: Vec<Vec<char>> = text
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .map(|line| line.trim_end().chars().collect())
+        .collect();
+
+    let lines: Vec<Vec<char>> = match lines.iter().rposition(|line| !line.is_empty()) {
+        Some(last) => lines[..=last].to_vec(),
+        None => return Err(MazeError::Parse("Empty maze.".to_string())),
+    };
+
+    if lines.len() < 3 || lines.len().is_multiple_of(2) {
+        return Err(MazeError::Parse(format!(
+            "Expected an odd number of lines (at least 3), got {}.",
+            lines.len()
+        )));
+    }
+
+    let top = &lines[0];
+    let cell_width = match top.iter().skip(1).position(|c| *c == '+' || *c == 'o') {
+        Some(p) if p > 0 => p,
+        _ => CELL_WIDTH,
+    };
+
+    let width = lines.iter().map(|line| line.len()).max().unwrap();
+    let rows = (lines.len() - 1) / 2;
+    let cols = (width - 1) / (cell_width + 1);
+
+    if rows == 0 || cols == 0 || (width - 1) % (cell_width + 1) != 0 {
+        return Err(MazeError::Parse(format!(
+            "Line width {} does not match a cell width of {}.",
+            width, cell_width
+        )));
+    }
+
+    // Missing characters, e.g. stripped trailing whitespace, count as spaces.
+    let at = |y: usize, x: usize| -> char {
+        return *lines[y].get(x).unwrap_or(&' ');
+    };
+
+    let mut start_cell = None;
+    let mut end_cell = None;
+    let mut exits: Vec<(usize, usize)> = vec![];
+    let mut solution: HashSet<(usize, usize)> = HashSet::new();
+    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+    let mut goals: Vec<(usize, usize)> = vec![];
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let y = 2 * row + 1;
+            let x = col * (cell_width + 1);
+            let content: String = (1..=cell_width).map(|i| at(y, x + i)).collect();
+
+            let (is_start, is_end) = (content.contains('S'), content.contains('E'));
+            if is_start {
+                start_cell = Some((row, col));
+            }
+            if is_end {
+                match end_cell {
+                    None => end_cell = Some((row, col)),
+                    Some(_) => exits.push((row, col)),
+                }
+            }
+
+            if is_start || is_end {
+                continue;
+            } else if content.contains('G') {
+                goals.push((row, col));
+            } else if content.contains('*') || content.contains('█') {
+                solution.insert((row, col));
+            } else if content.contains('.') || content.contains('░') {
+                visited.insert((row, col));
+            }
+        }
+    }
+
+    let start_cell = start_cell.unwrap_or((0, 0));
+    let end_cell = end_cell.unwrap_or((rows - 1, cols - 1));
+
+    let mut maze = WilsonMaze::new(rows, cols, start_cell, end_cell);
+    maze.exits = exits;
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let x = col * (cell_width + 1);
+
+            if col + 1 < cols && at(2 * row + 1, x + cell_width + 1) == ' ' {
+                maze.grid.carve(row, col, &Direction::Right);
+            }
+
+            if row + 1 < rows && at(2 * row + 2, x + 1) == ' ' {
+                maze.grid.carve(row, col, &Direction::Down);
+            }
+        }
+    }
+
+    if !solution.is_empty() {
+        let linked = |(row, col): (usize, usize)| -> Vec<(usize, usize)> {
+            return maze
+                .grid
+                .passages(row, col)
+                .map(|direction| maze.grid.neighbor(row, col, &direction).unwrap())
+                .collect();
+        };
+
+        // Follow the marked cells from the start to the end of the path.
+        let mut on_path: HashSet<(usize, usize)> = HashSet::from([start_cell]);
+        let mut last = start_cell;
+        while let Some(next) = linked(last)
+            .into_iter()
+            .find(|cell| solution.contains(cell) && !on_path.contains(cell))
+        {
+            on_path.insert(next);
+            last = next;
+        }
+
+        solution.insert(start_cell);
+        if let Some(end) = maze
+            .ends()
+            .into_iter()
+            .find(|end| linked(last).contains(end))
+        {
+            solution.insert(end);
+        }
+    }
+
+    return Ok(ParsedText {
+        maze: maze,
+        solution: solution,
+        visited: visited,
+        goals: goals,
+    });
+}
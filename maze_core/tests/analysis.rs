@@ -0,0 +1,148 @@
+use maze_core::{
+    analyze, difficulty_range, generate_with_difficulty, parse_text, Direction, Generator,
+    MazeError, WilsonMaze, DIFFICULTIES,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const FIXTURE: &str = include_str!("fixtures/wilson_5x7.txt");
+
+#[test]
+fn straight_corridor() {
+    let mut maze = WilsonMaze::new(1, 4, (0, 0), (0, 3));
+    for col in 0..3 {
+        maze.grid.carve(0, col, &Direction::Right);
+    }
+
+    let metrics = analyze(&maze);
+
+    assert_eq!(metrics.dead_ends, 2);
+    assert_eq!(metrics.junctions, 0);
+    assert_eq!(metrics.corridors, 2);
+    assert_eq!(metrics.longest_corridor, 2);
+    assert_eq!(metrics.solution_length, Some(4));
+    assert_eq!(metrics.branching_factor, 1.0);
+    assert_eq!(metrics.tortuosity, Some(1.0));
+    // Both dead ends are on the solution, so there are no side branches.
+    assert_eq!(metrics.river_factor, 0.0);
+}
+
+#[test]
+fn one_step_maze_is_easy() {
+    // A long corridor, the end is next to the start. The cell before the
+    // start is the only wrong turn, the rest lies past the end.
+    let mut maze = WilsonMaze::new(1, 20, (0, 1), (0, 2));
+    for col in 0..19 {
+        maze.grid.carve(0, col, &Direction::Right);
+    }
+
+    let metrics = analyze(&maze);
+
+    assert_eq!(metrics.solution_length, Some(2));
+    assert_eq!(metrics.branching_factor, 2.0);
+    assert_eq!(metrics.river_factor, 1.0);
+    let (min, max) = difficulty_range("easy").unwrap();
+    assert!(metrics.difficulty >= min && metrics.difficulty < max);
+}
+
+#[test]
+fn cross_with_unreachable_corners() {
+    // A plus sign, the corners have no passages at all.
+    let mut maze = WilsonMaze::new(3, 3, (1, 0), (0, 1));
+    maze.grid.carve(1, 0, &Direction::Right);
+    maze.grid.carve(1, 1, &Direction::Right);
+    maze.grid.carve(0, 1, &Direction::Down);
+    maze.grid.carve(1, 1, &Direction::Down);
+
+    let metrics = analyze(&maze);
+
+    assert_eq!(metrics.dead_ends, 4);
+    assert_eq!(metrics.junctions, 1);
+    assert_eq!(metrics.longest_corridor, 0);
+    assert_eq!(metrics.river_factor, 1.0);
+    // Three ways on from the centre, one from the start.
+    assert_eq!(metrics.branching_factor, 2.0);
+    assert_eq!(metrics.tortuosity, Some(1.0));
+
+    maze.end_cell = (0, 0);
+    let metrics = analyze(&maze);
+    assert_eq!(metrics.solution_length, None);
+    assert_eq!(metrics.tortuosity, None);
+}
+
+#[test]
+fn fixture_metrics() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let metrics = analyze(&parsed.maze);
+
+    assert_eq!(metrics.cells, 35);
+    assert_eq!(metrics.solution_length, Some(parsed.solution.len()));
+    assert_eq!(
+        metrics.tortuosity,
+        Some((parsed.solution.len() - 1) as f32 / 10.0)
+    );
+}
+
+#[test]
+fn every_cell_of_a_perfect_maze_is_classified() {
+    for generator in [Generator::Wilson, Generator::Backtracker] {
+        let mut maze = WilsonMaze::new(20, 25, (0, 0), (19, 24));
+        maze.generate_seeded(generator, 3).unwrap();
+
+        let metrics = analyze(&maze);
+
+        assert_eq!(
+            metrics.dead_ends + metrics.junctions + metrics.corridors,
+            metrics.cells
+        );
+        assert!(metrics.longest_corridor <= metrics.corridors);
+        assert!(metrics.tortuosity.unwrap() >= 1.0);
+    }
+}
+
+#[test]
+fn difficulty_grows_with_distance() {
+    let mut near = WilsonMaze::new(1, 4, (0, 0), (0, 1));
+    for col in 0..3 {
+        near.grid.carve(0, col, &Direction::Right);
+    }
+    let mut far = near.clone();
+    far.end_cell = (0, 3);
+
+    let near = analyze(&near).difficulty;
+    let far = analyze(&far).difficulty;
+
+    assert!(near > 0.0 && near < far && far < 100.0);
+}
+
+#[test]
+fn generates_each_difficulty() {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for (name, min, max) in DIFFICULTIES {
+        assert_eq!(difficulty_range(name), Some((min, max)));
+
+        let maze = generate_with_difficulty(15, 15, Generator::Wilson, (min, max), 1000, &mut rng)
+            .unwrap();
+        let score = analyze(&maze).difficulty;
+        assert!(score >= min && score <= max, "{}: {}", name, score);
+
+        // The maze can be generated again from its seed.
+        let mut again = WilsonMaze::new(15, 15, maze.start_cell, maze.end_cell);
+        again
+            .generate_seeded(Generator::Wilson, maze.seed.unwrap())
+            .unwrap();
+        assert_eq!(again.grid, maze.grid);
+    }
+}
+
+#[test]
+fn impossible_difficulty() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let result = generate_with_difficulty(5, 5, Generator::Wilson, (100.0, 100.0), 10, &mut rng);
+
+    assert!(matches!(
+        result,
+        Err(MazeError::DifficultyNotFound { attempts: 10 })
+    ));
+}
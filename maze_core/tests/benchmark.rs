@@ -0,0 +1,94 @@
+use maze_core::{
+    run_benchmark, runs_csv, summarize, summary_csv, summary_table, BenchmarkOptions, Generator,
+    MazeError, Stats,
+};
+
+fn options() -> BenchmarkOptions {
+    return BenchmarkOptions {
+        sizes: vec![(5, 5), (8, 6)],
+        generators: vec![Generator::Wilson, Generator::Backtracker],
+        solvers: vec!["bfs".to_string(), "backtrack".to_string()],
+        mazes: 3,
+        seed: 42,
+    };
+}
+
+#[test]
+fn stats() {
+    let stats = Stats::from_values(&[4.0, 1.0, 3.0, 2.0]);
+    assert_eq!(stats.mean, 2.5);
+    assert_eq!(stats.median, 2.5);
+    assert_eq!(stats.p95, 4.0);
+
+    let values: Vec<f64> = (1..=100).map(|v| v as f64).collect();
+    let stats = Stats::from_values(&values);
+    assert_eq!(stats.median, 50.5);
+    assert_eq!(stats.p95, 95.0);
+
+    assert_eq!(Stats::from_values(&[]).mean, 0.0);
+}
+
+#[test]
+fn runs_every_solver_on_every_maze() {
+    let runs = run_benchmark(&options()).unwrap();
+    assert_eq!(runs.len(), 2 * 2 * 3 * 2);
+
+    // Perfect mazes have a single path, so all solvers find the same one.
+    for pair in runs.chunks(2) {
+        assert_eq!(pair[0].seed, pair[1].seed);
+        assert_eq!(pair[0].path_length, pair[1].path_length);
+    }
+
+    let summaries = summarize(&runs);
+    assert_eq!(summaries.len(), 2 * 2 * 2);
+    assert!(summaries.iter().all(|s| s.runs == 3));
+    assert_eq!(
+        (
+            summaries[0].rows,
+            summaries[0].cols,
+            summaries[0].solver.as_str()
+        ),
+        (5, 5, "bfs")
+    );
+}
+
+#[test]
+fn same_seed_same_mazes() {
+    let a = run_benchmark(&options()).unwrap();
+    let b = run_benchmark(&options()).unwrap();
+
+    let lengths = |runs: &[maze_core::BenchmarkRun]| -> Vec<(usize, usize)> {
+        return runs.iter().map(|r| (r.visited, r.path_length)).collect();
+    };
+    assert_eq!(lengths(&a), lengths(&b));
+}
+
+#[test]
+fn csv_and_table() {
+    let runs = run_benchmark(&options()).unwrap();
+    let summaries = summarize(&runs);
+
+    let csv = runs_csv(&runs);
+    assert_eq!(csv.lines().count(), runs.len() + 1);
+    assert!(csv.starts_with("rows,cols,generator,seed,solver,"));
+
+    let csv = summary_csv(&summaries);
+    assert_eq!(csv.lines().count(), summaries.len() + 1);
+    assert!(csv.lines().all(|line| line.split(',').count() == 5 + 3 * 3));
+
+    let table = summary_table(&summaries);
+    assert_eq!(table.lines().count(), summaries.len() + 1);
+    let first: Vec<&str> = table.lines().nth(1).unwrap().split_whitespace().collect();
+    assert_eq!(first[..4], ["5x5", "wilson", "bfs", "3"]);
+}
+
+#[test]
+fn unknown_solver() {
+    let mut options = options();
+    options.solvers = vec!["teleport".to_string()];
+
+    assert_eq!(
+        run_benchmark(&options),
+        Err(MazeError::InvalidSolver("teleport".to_string()))
+    );
+}
@@ -0,0 +1,9 @@
+use maze_core::{Generator, WilsonMaze};
+
+/// A maze from the top left to the bottom right corner, generated with the
+/// given generator and seed.
+pub fn generated(rows: usize, cols: usize, generator: Generator, seed: u64) -> WilsonMaze {
+    let mut maze = WilsonMaze::new(rows, cols, (0, 0), (rows - 1, cols - 1));
+    maze.generate_seeded(generator, seed).unwrap();
+    return maze;
+}
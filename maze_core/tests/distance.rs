@@ -0,0 +1,69 @@
+use maze_core::{
+    diameter, distance_field, farthest_cell, parse_text, Direction, Generator, WilsonMaze,
+};
+
+const FIXTURE: &str = include_str!("fixtures/wilson_5x7.txt");
+
+#[test]
+fn farthest_in_a_corridor() {
+    let mut maze = WilsonMaze::new(1, 5, (0, 2), (0, 4));
+    for col in 0..4 {
+        maze.grid.carve(0, col, &Direction::Right);
+    }
+
+    // Both ends are two steps away, the first one wins.
+    assert_eq!(farthest_cell(&maze, (0, 2)), ((0, 0), 2));
+    assert_eq!(farthest_cell(&maze, (0, 0)), ((0, 4), 4));
+}
+
+#[test]
+fn farthest_matches_distance_field() {
+    let maze = parse_text(FIXTURE).unwrap().maze;
+    let distances = distance_field(&maze, maze.start_cell);
+
+    let ((row, col), steps) = farthest_cell(&maze, maze.start_cell);
+
+    assert_eq!(distances[row * maze.cols + col], Some(steps));
+    assert_eq!(distances.iter().flatten().max(), Some(&steps));
+}
+
+#[test]
+fn farthest_without_passages() {
+    let maze = WilsonMaze::new(3, 3, (1, 1), (2, 2));
+
+    assert_eq!(farthest_cell(&maze, (1, 1)), ((1, 1), 0));
+}
+
+#[test]
+fn diameter_of_an_l_shape() {
+    // (0,0) - (0,1) - (0,2)
+    //                   |
+    //                 (1,2)
+    let mut maze = WilsonMaze::new(2, 3, (0, 1), (1, 2));
+    maze.grid.carve(0, 0, &Direction::Right);
+    maze.grid.carve(0, 1, &Direction::Right);
+    maze.grid.carve(0, 2, &Direction::Down);
+
+    let (a, b, steps) = diameter(&maze);
+
+    assert_eq!(steps, 3);
+    assert!((a, b) == ((0, 0), (1, 2)) || (a, b) == ((1, 2), (0, 0)));
+}
+
+#[test]
+fn diameter_is_the_longest_distance() {
+    for generator in [Generator::Wilson, Generator::Backtracker] {
+        let mut maze = WilsonMaze::new(12, 9, (0, 0), (11, 8));
+        maze.generate_seeded(generator, 7).unwrap();
+
+        let (a, b, steps) = diameter(&maze);
+        assert_eq!(distance_field(&maze, a)[b.0 * maze.cols + b.1], Some(steps));
+
+        // Brute force over all pairs.
+        let longest = (0..maze.rows * maze.cols)
+            .flat_map(|i| distance_field(&maze, (i / maze.cols, i % maze.cols)))
+            .flatten()
+            .max();
+        assert_eq!(longest, Some(steps));
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashSet;
+
+use maze_core::{
+    cell_backgrounds, distance_field, get_bfs_solution, gradient_colour, parse_text, rasterize,
+    render_gif, render_pdf, render_png, render_svg, repaint_cell, AnimationOptions, Colours,
+    Generator, MazeError, PdfOptions, Priority, RasterOptions, Solution, SvgOptions, WilsonMaze,
+};
+
+const FIXTURE: &str = include_str!("fixtures/wilson_5x7.txt");
+
+#[test]
+fn svg_has_configured_size_and_colours() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let options = SvgOptions {
+        cell_size: 20.0,
+        margin: 5.0,
+        colours: Colours::print(),
+        ..Default::default()
+    };
+
+    let svg = render_svg(&parsed.maze, &options);
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains(r#"width="150" height="110""#));
+    assert!(svg.contains(r##"fill="#ffffff""##));
+    assert!(svg.contains(r##"stroke="#000000""##));
+}
+
+#[test]
+fn svg_draws_every_inner_wall_once() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let svg = render_svg(&parsed.maze, &SvgOptions::default());
+
+    // A 5x7 perfect maze has 35 - 1 passages out of 4 * 7 + 5 * 6 inner walls.
+    let walls = svg
+        .lines()
+        .find(|line| line.contains("stroke="))
+        .unwrap()
+        .matches('M')
+        .count();
+    assert_eq!(walls, 1 + (4 * 7 + 5 * 6) - 34);
+}
+
+#[test]
+fn svg_marks_solution_cells() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let options = SvgOptions {
+        solution: Some(&parsed.solution),
+        ..Default::default()
+    };
+
+    let svg = render_svg(&parsed.maze, &options);
+    let solution_line = svg
+        .lines()
+        .find(|line| line.contains(r##"fill="#e95620" fill-opacity="1""##))
+        .unwrap();
+
+    assert_eq!(solution_line.matches('M').count(), parsed.solution.len());
+}
+
+#[test]
+fn raster_uses_theme_colours() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let colours = Colours::dark();
+    let options = RasterOptions {
+        cell_size: 10,
+        wall_thickness: 2,
+        margin: 4,
+        solution: Some(&parsed.solution),
+        ..Default::default()
+    };
+
+    let canvas = rasterize(&parsed.maze, &options);
+
+    assert_eq!((canvas.width, canvas.height), (7 * 10 + 8, 5 * 10 + 8));
+    // Margin, top left corner of the outer wall, and the start marker.
+    assert_eq!(canvas.pixel(0, 0), colours.background);
+    assert_eq!(canvas.pixel(3, 3), colours.wall);
+    assert_eq!(canvas.pixel(4 + 5, 4 + 5), colours.start);
+    // The start cell has a wall to its right, the cell below it is on the
+    // solution and open to the right.
+    assert_eq!(canvas.pixel(4 + 10, 4 + 2), colours.wall);
+    assert_eq!(canvas.pixel(4 + 5, 4 + 15), colours.solution);
+    assert_eq!(canvas.pixel(4 + 10, 4 + 12), colours.background);
+}
+
+#[test]
+fn raster_gradient_grows_with_distance() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let colours = Colours::print();
+    let options = RasterOptions {
+        cell_size: 10,
+        margin: 4,
+        colours: colours,
+        gradient: true,
+        ..Default::default()
+    };
+
+    let canvas = rasterize(&parsed.maze, &options);
+    let distances = distance_field(&parsed.maze, parsed.maze.start_cell);
+    let max_distance = distances.iter().flatten().max().cloned().unwrap() as f32;
+
+    // (1, 0) is one step from the start, (0, 1) is three.
+    assert_eq!(distances[7], Some(1));
+    assert_eq!(distances[1], Some(3));
+    assert_eq!(
+        canvas.pixel(4 + 2, 4 + 12),
+        gradient_colour(&colours, 1.0 / max_distance)
+    );
+    assert_eq!(
+        canvas.pixel(4 + 12, 4 + 2),
+        gradient_colour(&colours, 3.0 / max_distance)
+    );
+}
+
+#[test]
+fn png_decodes_to_raster() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let options = RasterOptions::default();
+
+    let bytes = render_png(&parsed.maze, &options).unwrap();
+    let canvas = rasterize(&parsed.maze, &options);
+
+    let decoder = png::Decoder::new(bytes.as_slice());
+    let mut reader = decoder.read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+
+    assert_eq!(info.width as usize, canvas.width);
+    assert_eq!(info.height as usize, canvas.height);
+    assert_eq!(pixels, canvas.pixels);
+}
+
+#[test]
+fn gif_has_a_frame_per_step() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let solution = get_bfs_solution(&parsed.maze, Priority::Disabled).unwrap();
+    let options = AnimationOptions {
+        fps: 10,
+        frames_per_step: 2,
+        hold_seconds: 3,
+        ..Default::default()
+    };
+
+    let bytes = render_gif(&parsed.maze, &solution, &options).unwrap();
+
+    let mut decoder = gif::DecodeOptions::new();
+    decoder.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = decoder.read_info(bytes.as_slice()).unwrap();
+    let mut delays: Vec<u16> = vec![];
+    let mut last: Vec<u8> = vec![];
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+        last = frame.buffer.to_vec();
+    }
+
+    // An empty frame, one per visited cell and the solution.
+    assert_eq!(delays.len(), solution.visited.len() + 2);
+    assert!(delays[..delays.len() - 1].iter().all(|delay| *delay == 20));
+    assert_eq!(delays[delays.len() - 1], 300);
+
+    let visited = solution.visited.iter().cloned().collect();
+    let canvas = rasterize(
+        &parsed.maze,
+        &RasterOptions {
+            solution: Some(&solution.path_cells()),
+            visited: Some(&visited),
+            ..options.raster
+        },
+    );
+    assert_eq!(last, canvas.pixels);
+}
+
+#[test]
+fn gif_rejects_huge_images_before_drawing() {
+    // 90000 px wide, the canvas alone would take over 30 GB.
+    let maze = WilsonMaze::new(300, 300, (0, 0), (299, 299));
+    let solution = Solution {
+        path: vec![(0, 0)],
+        visited: vec![(0, 0)],
+    };
+    let options = AnimationOptions {
+        raster: RasterOptions {
+            cell_size: 300,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        render_gif(&maze, &solution, &options),
+        Err(MazeError::Export(_))
+    ));
+}
+
+#[test]
+fn repainted_cells_match_the_raster() {
+    let mut maze = WilsonMaze::new(9, 11, (4, 5), (0, 0));
+    maze.generate_seeded(Generator::Braid, 4).unwrap();
+    maze.exits = vec![(8, 10)];
+    let solution = get_bfs_solution(&maze, Priority::Disabled).unwrap();
+    let path = solution.path_cells();
+
+    for gradient in [false, true] {
+        let empty = RasterOptions {
+            cell_size: 12,
+            wall_thickness: 3,
+            gradient: gradient,
+            ..Default::default()
+        };
+        let backgrounds = cell_backgrounds(&maze, &empty);
+        let mut canvas = rasterize(&maze, &empty);
+        let mut visited: HashSet<(usize, usize)> = HashSet::new();
+
+        for cell in &solution.visited {
+            visited.insert(*cell);
+            let options = RasterOptions {
+                visited: Some(&visited),
+                ..empty
+            };
+            repaint_cell(&mut canvas, &maze, &options, &backgrounds, *cell);
+
+            assert_eq!(canvas, rasterize(&maze, &options));
+        }
+
+        let options = RasterOptions {
+            visited: Some(&visited),
+            solution: Some(&path),
+            ..empty
+        };
+        for cell in &path {
+            repaint_cell(&mut canvas, &maze, &options, &backgrounds, *cell);
+        }
+        assert_eq!(canvas, rasterize(&maze, &options));
+    }
+}
+
+#[test]
+fn pdf_has_pages_and_answer_key() {
+    let mazes: Vec<WilsonMaze> = (0..5)
+        .map(|seed| {
+            let mut maze = WilsonMaze::new(6, 8, (0, 0), (5, 7));
+            maze.generate_seeded(Generator::Wilson, seed).unwrap();
+            maze
+        })
+        .collect();
+    let options = PdfOptions {
+        title: "Practice",
+        mazes_per_page: 2,
+        answer_key: true,
+        ..Default::default()
+    };
+
+    let bytes = render_pdf(&mazes, &options).unwrap();
+    let text = String::from_utf8_lossy(&bytes);
+
+    assert!(text.starts_with("%PDF-"));
+    // Three pages of mazes, and three of answers.
+    assert_eq!(text.matches("/Type /Page\n").count(), 6);
+    assert!(text.contains("(Practice - answers)"));
+    assert!(text.contains("(Practice    Page 1 of 6    Seed: 0, 1)"));
+    assert!(text.contains("(Practice    Page 6 of 6    Seed: 4)"));
+}
+
+#[test]
+fn pdf_rejects_empty_worksheet() {
+    assert!(render_pdf(&[], &PdfOptions::default()).is_err());
+}
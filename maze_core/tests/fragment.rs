@@ -0,0 +1,99 @@
+use maze_core::{from_fragment, parse_text, to_fragment, Generator, MazeError, WilsonMaze};
+
+const FIXTURE: &str = include_str!("fixtures/wilson_5x7.txt");
+
+fn assert_same_maze(a: &WilsonMaze, b: &WilsonMaze) {
+    assert_eq!(a.grid, b.grid);
+    assert_eq!((a.start_cell, a.end_cell), (b.start_cell, b.end_cell));
+}
+
+#[test]
+fn seeded_maze_stores_only_the_seed() {
+    let mut maze = WilsonMaze::new(12, 30, (4, 2), (11, 0));
+    maze.generate_seeded(Generator::Backtracker, 1234).unwrap();
+
+    let fragment = to_fragment(&maze, Some("bfs-close"));
+    assert_eq!(
+        fragment,
+        "v=3&size=12x30&start=4,2&end=11,0&gen=backtracker&seed=1234&solver=bfs-close"
+    );
+
+    let shared = from_fragment(&format!("#{}", fragment)).unwrap();
+    assert_same_maze(&maze, &shared.maze);
+    assert_eq!(shared.maze.seed, Some(1234));
+    assert_eq!(shared.solver.as_deref(), Some("bfs-close"));
+}
+
+#[test]
+fn other_mazes_store_their_passages() {
+    let maze = parse_text(FIXTURE).unwrap().maze;
+
+    let fragment = to_fragment(&maze, None);
+    // Two bits for each of the 35 cells fit in 9 bytes, or 12 characters.
+    let grid = fragment.split("grid=").nth(1).unwrap();
+    assert_eq!(grid.len(), 12);
+
+    let shared = from_fragment(&fragment).unwrap();
+    assert_same_maze(&maze, &shared.maze);
+    assert_eq!(shared.solver, None);
+}
+
+#[test]
+fn unknown_solvers_are_left_out() {
+    let maze = parse_text(FIXTURE).unwrap().maze;
+
+    assert!(!to_fragment(&maze, Some("teleport")).contains("solver"));
+}
+
+#[test]
+fn rejects_broken_fragments() {
+    let maze = parse_text(FIXTURE).unwrap().maze;
+    let fragment = to_fragment(&maze, None);
+
+    for broken in [
+        String::new(),
+        fragment.replace("v=3", "v=4"),
+        fragment.replace("v=3", "v=0"),
+        fragment.replace("size=5x7", "size=5x8"),
+        fragment.replace("end=4,6", "end=5,6"),
+        fragment.replace("size=5x7", "size=100000x100000"),
+        "v=1&size=2x2&start=0,0&end=1,1&grid=_w".to_string(),
+        "v=1&size=2x2&start=0,0&end=1,1".to_string(),
+        "v=1&size=2x2&gen=prim&seed=1".to_string(),
+        "v=3&size=3x3&start=4294967296,0&end=2,2&gen=wilson&seed=1".to_string(),
+    ] {
+        assert!(from_fragment(&broken).is_err(), "{}", broken);
+    }
+
+    assert_eq!(
+        from_fragment(&format!("{}&solver=teleport", fragment)).unwrap_err(),
+        MazeError::InvalidSolver("teleport".to_string())
+    );
+}
+
+#[test]
+fn exits_are_listed_after_the_end() {
+    let mut maze = WilsonMaze::new(6, 6, (0, 0), (5, 5));
+    maze.generate_seeded(Generator::Wilson, 9).unwrap();
+    maze.exits = vec![(0, 5), (5, 0)];
+
+    let fragment = to_fragment(&maze, None);
+    assert!(fragment.contains("&end=5,5&exits=0,5;5,0&"));
+
+    let shared = from_fragment(&fragment).unwrap();
+    assert_same_maze(&maze, &shared.maze);
+    assert_eq!(shared.maze.exits, maze.exits);
+
+    assert!(from_fragment(&fragment.replace("5,0&", "6,0&")).is_err());
+}
+
+#[test]
+fn reads_version_one_links() {
+    let mut maze = WilsonMaze::new(12, 30, (4, 2), (11, 0));
+    maze.generate_seeded(Generator::Backtracker, 1234).unwrap();
+
+    let shared =
+        from_fragment("v=1&size=12x30&start=4,2&end=11,0&gen=backtracker&seed=1234").unwrap();
+
+    assert_same_maze(&maze, &shared.maze);
+}
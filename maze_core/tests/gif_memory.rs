@@ -0,0 +1,59 @@
+//! Runs on its own, since the allocator counts the allocations of every
+//! test in the binary.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use maze_core::{get_bfs_solution, render_gif, AnimationOptions, Generator, Priority, WilsonMaze};
+
+/// Keeps track of the most memory allocated at any time.
+struct PeakAlloc {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        self.peak.fetch_max(current, Ordering::SeqCst);
+        return System.alloc(layout);
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc {
+    current: AtomicUsize::new(0),
+    peak: AtomicUsize::new(0),
+};
+
+#[test]
+fn gif_memory_does_not_grow_with_the_steps() {
+    let mut maze = WilsonMaze::new(50, 50, (0, 0), (49, 49));
+    maze.generate_seeded(Generator::Wilson, 1).unwrap();
+    let solution = get_bfs_solution(&maze, Priority::Disabled).unwrap();
+    // The default cell size of the command line.
+    let mut options = AnimationOptions::default();
+    options.raster.cell_size = 30;
+
+    let before = ALLOCATOR.current.load(Ordering::SeqCst);
+    ALLOCATOR.peak.store(before, Ordering::SeqCst);
+
+    let bytes = render_gif(&maze, &solution, &options).unwrap();
+
+    // A canvas of 1520x1520 px is about 9 MB, and every step would add
+    // another one if the frames were kept.
+    let used = ALLOCATOR.peak.load(Ordering::SeqCst) - before;
+    assert!(solution.visited.len() > 100);
+    assert!(
+        used < 64 * 1024 * 1024,
+        "{} MB for {} frames",
+        used / (1024 * 1024),
+        solution.visited.len() + 2
+    );
+    assert!(bytes.len() < used);
+}
@@ -0,0 +1,114 @@
+use maze_core::{
+    get_bfs_solution, import_image, import_png, parse_text, rasterize, render_png, Canvas, Colours,
+    Generator, ImportOptions, Priority, RasterOptions, WilsonMaze,
+};
+
+const FIXTURE: &str = include_str!("fixtures/wilson_5x7.txt");
+
+#[test]
+fn png_round_trips_with_markers() {
+    let mut maze = WilsonMaze::new(12, 17, (3, 4), (10, 2));
+    maze.generate_seeded(Generator::Wilson, 5).unwrap();
+
+    for (colours, cell_size, wall_thickness) in [
+        (Colours::print(), 30, 2),
+        (Colours::print(), 7, 1),
+        (Colours::print(), 16, 3),
+        (Colours::dark(), 12, 2),
+    ] {
+        let options = RasterOptions {
+            cell_size: cell_size,
+            wall_thickness: wall_thickness,
+            colours: colours,
+            ..Default::default()
+        };
+        let bytes = render_png(&maze, &options).unwrap();
+
+        let imported = import_png(
+            &bytes,
+            &ImportOptions {
+                colours: colours,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(maze.grid, imported.grid);
+        if colours.start != colours.end {
+            assert_eq!(imported.start_cell, maze.start_cell);
+            assert_eq!(imported.end_cell, maze.end_cell);
+        }
+    }
+}
+
+#[test]
+fn solution_overlay_is_not_a_wall() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let options = RasterOptions {
+        colours: Colours::print(),
+        solution: Some(&parsed.solution),
+        ..Default::default()
+    };
+
+    let imported = import_image(
+        &rasterize(&parsed.maze, &options),
+        &ImportOptions {
+            colours: Colours::print(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(parsed.maze.grid, imported.grid);
+    assert_eq!(imported.end_cell, parsed.maze.end_cell);
+    assert_eq!(
+        get_bfs_solution(&imported, Priority::Disabled)
+            .unwrap()
+            .path_cells(),
+        parsed.solution
+    );
+}
+
+#[test]
+fn openings_in_the_outer_wall_are_start_and_end() {
+    let parsed = parse_text(FIXTURE).unwrap();
+    let colours = Colours::print();
+    // Same colour as the background, so there are no markers.
+    let options = RasterOptions {
+        cell_size: 10,
+        wall_thickness: 2,
+        margin: 4,
+        colours: Colours {
+            start: colours.background,
+            end: colours.background,
+            ..colours
+        },
+        ..Default::default()
+    };
+    let mut canvas: Canvas = rasterize(&parsed.maze, &options);
+
+    // Entrance on the left of (1, 0), exit at the bottom of (4, 5).
+    canvas.fill_rect(3, 4 + 10 + 2, 2, 6, colours.background);
+    canvas.fill_rect(4 + 50 + 2, 4 + 49, 6, 2, colours.background);
+
+    let imported = import_image(
+        &canvas,
+        &ImportOptions {
+            colours: colours,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(parsed.maze.grid, imported.grid);
+    assert_eq!(imported.start_cell, (1, 0));
+    assert_eq!(imported.end_cell, (4, 5));
+}
+
+#[test]
+fn rejects_images_without_a_maze() {
+    let blank = Canvas::new(40, 30, [0xff, 0xff, 0xff, 0xff]);
+
+    assert!(import_image(&blank, &ImportOptions::default()).is_err());
+    assert!(import_png(b"not a png", &ImportOptions::default()).is_err());
+}
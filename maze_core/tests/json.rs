@@ -0,0 +1,105 @@
+use maze_core::{
+    from_json, solve, to_json, Generator, MazeError, SolverRun, WilsonMaze, JSON_VERSION,
+};
+
+mod common;
+use common::generated;
+
+#[test]
+fn round_trip_maze_and_run() {
+    let maze = generated(8, 13, Generator::Backtracker, 5);
+    let run = SolverRun {
+        solver: "backtrack".to_string(),
+        solution: solve(&maze, "backtrack").unwrap(),
+    };
+
+    let (loaded, loaded_run) = from_json(&to_json(&maze, Some(&run)).unwrap()).unwrap();
+
+    assert_eq!(loaded.grid, maze.grid);
+    assert_eq!(loaded.start_cell, maze.start_cell);
+    assert_eq!(loaded.end_cell, maze.end_cell);
+    assert_eq!(loaded.generator, Some(Generator::Backtracker));
+    assert_eq!(loaded.seed, Some(5));
+    assert_eq!(loaded_run, Some(run));
+}
+
+#[test]
+fn round_trip_without_run() {
+    let maze = generated(8, 13, Generator::Backtracker, 5);
+    let text = to_json(&maze, None).unwrap();
+
+    assert!(!text.contains("\"run\""));
+    assert_eq!(from_json(&text).unwrap().1, None);
+}
+
+#[test]
+fn serde_on_maze_uses_schema() {
+    let maze = generated(8, 13, Generator::Backtracker, 5);
+    let value = serde_json::to_value(&maze).unwrap();
+
+    assert_eq!(value["version"], JSON_VERSION);
+    assert_eq!(value["generator"], "backtracker");
+    assert_eq!(value["passages"].as_array().unwrap().len(), 8 * 13);
+
+    let loaded: WilsonMaze = serde_json::from_value(value).unwrap();
+    assert_eq!(loaded.grid, maze.grid);
+}
+
+#[test]
+fn rejects_invalid_records() {
+    let valid = r#"{
+        "version": 1, "rows": 1, "cols": 2,
+        "start_cell": [0, 0], "end_cell": [0, 1],
+        "generator": null, "seed": null,
+        "passages": [["right"], ["left"]]
+    }"#;
+    assert!(from_json(valid).is_ok());
+
+    let cases = [
+        valid.replace("\"version\": 1", "\"version\": 99"),
+        valid.replace("\"version\": 1", "\"version\": 0"),
+        valid.replace("[[\"right\"], [\"left\"]]", "[[\"right\"]]"),
+        valid.replace("[[\"right\"], [\"left\"]]", "[[\"up\"], []]"),
+        valid.replace("\"end_cell\": [0, 1]", "\"end_cell\": [3, 1]"),
+        valid.replace("\"start_cell\": [0, 0]", "\"start_cell\": [4294967296, 0]"),
+        // Sizes that overflow, are too large or are empty.
+        valid.replace(
+            "\"rows\": 1, \"cols\": 2",
+            "\"rows\": 4294967296, \"cols\": 4294967296",
+        ),
+        valid.replace("\"rows\": 1, \"cols\": 2", "\"rows\": 1, \"cols\": 4000001"),
+        valid.replace("\"rows\": 1, \"cols\": 2", "\"rows\": 0, \"cols\": 2"),
+        "not json".to_string(),
+    ];
+
+    for case in cases {
+        let error = from_json(&case).unwrap_err();
+        assert!(
+            matches!(error, MazeError::Parse(_) | MazeError::OutOfBounds { .. }),
+            "{}",
+            case
+        );
+    }
+}
+
+#[test]
+fn round_trip_exits() {
+    let mut maze = generated(8, 13, Generator::Backtracker, 5);
+    assert!(!to_json(&maze, None).unwrap().contains("exits"));
+
+    maze.exits = vec![(0, 12), (7, 0)];
+    let (loaded, _) = from_json(&to_json(&maze, None).unwrap()).unwrap();
+
+    assert_eq!(loaded.exits, maze.exits);
+}
+
+#[test]
+fn rejects_exits_outside_the_maze() {
+    let mut maze = generated(8, 13, Generator::Backtracker, 5);
+    maze.exits = vec![(8, 0)];
+
+    assert!(matches!(
+        from_json(&to_json(&maze, None).unwrap()),
+        Err(MazeError::OutOfBounds { .. })
+    ));
+}
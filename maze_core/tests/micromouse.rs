@@ -0,0 +1,94 @@
+use maze_core::{
+    centre_goal, get_bfs_solution, parse_maz, parse_micromouse_text, render_maz,
+    render_micromouse_text, Direction, Generator, Priority, WilsonMaze,
+};
+
+const FIXTURE: &str = include_str!("fixtures/micromouse_4x4.txt");
+
+#[test]
+fn centre_goal_is_two_by_two() {
+    assert_eq!(centre_goal(16, 16), vec![(7, 7), (7, 8), (8, 7), (8, 8)]);
+    assert_eq!(centre_goal(5, 4), vec![(2, 1), (2, 2)]);
+}
+
+#[test]
+fn text_uses_closest_goal_cell() {
+    let maze = parse_micromouse_text(FIXTURE).unwrap();
+
+    assert_eq!((maze.rows, maze.cols), (4, 4));
+    assert_eq!(maze.start_cell, (3, 0));
+    // (1, 1) is entered from above, five steps from the start.
+    assert_eq!(maze.end_cell, (1, 1));
+    assert!(maze.grid.has_passage(0, 1, &Direction::Down));
+    assert!(!maze.grid.has_passage(1, 2, &Direction::Right));
+
+    let solution = get_bfs_solution(&maze, Priority::Disabled).unwrap();
+    assert_eq!(solution.path.len(), 6);
+}
+
+#[test]
+fn text_round_trip() {
+    let maze = parse_micromouse_text(FIXTURE).unwrap();
+
+    assert_eq!(render_micromouse_text(&maze), FIXTURE);
+}
+
+#[test]
+fn text_defaults_to_micromouse_start_and_goal() {
+    let text = FIXTURE.replace(['S', 'G'], " ");
+    let maze = parse_micromouse_text(&text).unwrap();
+
+    assert_eq!(maze.start_cell, (3, 0));
+    assert_eq!(maze.end_cell, (1, 1));
+}
+
+#[test]
+fn maz_wall_bits() {
+    // (0, 0) is open to the right, (0, 1) is open below.
+    let mut maze = WilsonMaze::new(2, 2, (1, 0), (0, 1));
+    maze.grid.carve(0, 0, &Direction::Right);
+    maze.grid.carve(0, 1, &Direction::Down);
+
+    // Column by column from the bottom left, with N=1, E=2, S=4 and W=8.
+    assert_eq!(render_maz(&maze).unwrap(), vec![15, 13, 14, 3]);
+}
+
+#[test]
+fn maz_round_trip() {
+    let mut maze = WilsonMaze::new(16, 16, (15, 0), (7, 7));
+    maze.generate_seeded(Generator::Backtracker, 11).unwrap();
+
+    let bytes = render_maz(&maze).unwrap();
+    assert_eq!(bytes.len(), 256);
+
+    let parsed = parse_maz(&bytes).unwrap();
+    assert_eq!(maze.grid, parsed.grid);
+    assert_eq!(parsed.start_cell, (15, 0));
+    assert!(centre_goal(16, 16).contains(&parsed.end_cell));
+
+    let text = render_micromouse_text(&parsed);
+    assert_eq!(parsed.grid, parse_micromouse_text(&text).unwrap().grid);
+}
+
+#[test]
+fn maz_rejects_bad_sizes() {
+    assert!(parse_maz(&[0; 255]).is_err());
+    assert!(render_maz(&WilsonMaze::new(3, 4, (0, 0), (2, 3))).is_err());
+}
+
+#[test]
+fn every_goal_cell_is_an_end() {
+    let maze = parse_micromouse_text(FIXTURE).unwrap();
+
+    let mut ends = maze.ends();
+    ends.sort();
+    assert_eq!(ends, vec![(1, 1), (1, 2), (2, 1), (2, 2)]);
+    assert!(!maze.exits.contains(&maze.end_cell));
+
+    // Without the closest goal cell, the solver stops at another one.
+    let mut maze = maze;
+    maze.end_cell = maze.exits.pop().unwrap();
+    let solution = get_bfs_solution(&maze, Priority::Disabled).unwrap();
+    assert!(maze.is_end(*solution.path.last().unwrap()));
+    assert_ne!(solution.path.last(), Some(&(1, 1)));
+}
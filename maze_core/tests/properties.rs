@@ -0,0 +1,151 @@
+//! Invariants that hold for every generated maze and every solver, checked
+//! on random sizes, seeds and start and end cells.
+use proptest::prelude::*;
+
+use maze_core::{
+    direction_reverse, distance_field, solve, Direction, Generator, WilsonMaze, SOLVERS,
+};
+
+/// Generators without loops.
+const PERFECT: [Generator; 2] = [Generator::Wilson, Generator::Backtracker];
+
+fn generator() -> impl Strategy<Value = Generator> {
+    return prop_oneof![
+        Just(Generator::Wilson),
+        Just(Generator::Backtracker),
+        Just(Generator::Braid)
+    ];
+}
+
+/// A generated maze with start and end cells anywhere in it.
+fn maze() -> impl Strategy<Value = WilsonMaze> {
+    return (2..16usize, 2..16usize, generator(), any::<u64>())
+        .prop_flat_map(|(rows, cols, generator, seed)| {
+            (
+                Just((rows, cols, generator, seed)),
+                (0..rows, 0..cols),
+                (0..rows, 0..cols),
+            )
+        })
+        .prop_map(|((rows, cols, generator, seed), start, end)| {
+            let mut maze = WilsonMaze::new(rows, cols, start, end);
+            maze.generate_seeded(generator, seed).unwrap();
+            maze
+        });
+}
+
+fn passages(maze: &WilsonMaze) -> usize {
+    let mut count = 0;
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            count += maze.grid.has_passage(row, col, &Direction::Right) as usize;
+            count += maze.grid.has_passage(row, col, &Direction::Down) as usize;
+        }
+    }
+    return count;
+}
+
+proptest! {
+    #[test]
+    fn generated_mazes_are_connected(maze in maze()) {
+        let distances = distance_field(&maze, (0, 0));
+
+        prop_assert!(distances.iter().all(|d| d.is_some()));
+    }
+
+    #[test]
+    fn passages_are_symmetric(maze in maze()) {
+        for row in 0..maze.rows {
+            for col in 0..maze.cols {
+                for direction in maze.grid.passages(row, col) {
+                    let neighbor = maze.grid.neighbor(row, col, &direction);
+                    prop_assert!(
+                        neighbor.is_some(),
+                        "passage out of the maze at ({}, {})",
+                        row,
+                        col
+                    );
+
+                    let (nrow, ncol) = neighbor.unwrap();
+                    prop_assert!(
+                        maze.grid.has_passage(nrow, ncol, &direction_reverse(&direction))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn perfect_mazes_are_trees(maze in maze()) {
+        prop_assume!(PERFECT.contains(&maze.generator.unwrap()));
+
+        // Connected with one passage less than cells means no loops.
+        prop_assert_eq!(passages(&maze), maze.rows * maze.cols - 1);
+    }
+
+    #[test]
+    fn braided_mazes_have_no_dead_ends(maze in maze()) {
+        prop_assume!(maze.generator == Some(Generator::Braid));
+
+        for row in 0..maze.rows {
+            for col in 0..maze.cols {
+                prop_assert!(maze.grid.passages(row, col).count() >= 2);
+            }
+        }
+    }
+
+    #[test]
+    fn solutions_connect_start_and_end(maze in maze()) {
+        for (solver, _) in SOLVERS {
+            let solution = solve(&maze, solver).unwrap();
+            let path = &solution.path;
+
+            prop_assert_eq!(path.first(), Some(&maze.start_cell), "{}", solver);
+            prop_assert_eq!(path.last(), Some(&maze.end_cell), "{}", solver);
+            prop_assert_eq!(
+                solution.path_cells().len(),
+                path.len(),
+                "{} repeats a cell",
+                solver
+            );
+
+            // Every step goes through a passage.
+            for (step, direction) in path.windows(2).zip(solution.directions()) {
+                let ((row, col), next) = (step[0], step[1]);
+                prop_assert!(maze.grid.has_passage(row, col, &direction), "{}", solver);
+                prop_assert_eq!(
+                    maze.grid.neighbor(row, col, &direction),
+                    Some(next),
+                    "{}",
+                    solver
+                );
+            }
+
+            // Every cell on the path was visited by the solver.
+            prop_assert!(
+                path.iter().all(|cell| solution.visited.contains(cell)),
+                "{}",
+                solver
+            );
+        }
+    }
+
+    #[test]
+    fn bfs_finds_a_shortest_path(maze in maze()) {
+        let end = maze.end_cell.0 * maze.cols + maze.end_cell.1;
+        let shortest = distance_field(&maze, maze.start_cell)[end].unwrap();
+
+        prop_assert_eq!(solve(&maze, "bfs").unwrap().steps(), shortest);
+    }
+
+    #[test]
+    fn solvers_agree_on_perfect_mazes(maze in maze()) {
+        prop_assume!(PERFECT.contains(&maze.generator.unwrap()));
+
+        // There is only one path, so every solver has to find it.
+        let path = solve(&maze, "bfs").unwrap().path;
+        for (solver, _) in SOLVERS {
+            prop_assert_eq!(&solve(&maze, solver).unwrap().path, &path, "{}", solver);
+        }
+    }
+}
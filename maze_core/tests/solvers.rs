@@ -0,0 +1,155 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use maze_core::{
+    braid, distance_field, get_bfs_solution, solve, Direction, Generator, Priority, WilsonMaze,
+    SOLVERS,
+};
+
+mod common;
+use common::generated;
+
+fn distance(maze: &WilsonMaze, from: (usize, usize), to: (usize, usize)) -> usize {
+    return distance_field(maze, from)[to.0 * maze.cols + to.1].unwrap();
+}
+
+#[test]
+fn bfs_takes_the_short_way_around_a_loop() {
+    // A ring of six cells, the end is right below the start.
+    let mut maze = WilsonMaze::new(2, 3, (0, 0), (1, 0));
+    maze.grid.carve(0, 0, &Direction::Right);
+    maze.grid.carve(0, 1, &Direction::Right);
+    maze.grid.carve(0, 2, &Direction::Down);
+    maze.grid.carve(1, 2, &Direction::Left);
+    maze.grid.carve(1, 1, &Direction::Left);
+    maze.grid.carve(1, 0, &Direction::Up);
+
+    let solution = get_bfs_solution(&maze, Priority::Disabled).unwrap();
+
+    assert_eq!(solution.path, vec![(0, 0), (1, 0)]);
+}
+
+#[test]
+fn bfs_is_shortest_on_braided_mazes() {
+    for seed in 0..20 {
+        let maze = generated(15, 20, Generator::Braid, seed);
+        let solution = solve(&maze, "bfs").unwrap();
+
+        assert_eq!(
+            solution.steps(),
+            distance(&maze, maze.start_cell, maze.end_cell),
+            "seed {}",
+            seed
+        );
+    }
+}
+
+#[test]
+fn bfs_visits_cells_in_order_of_distance() {
+    let maze = generated(12, 12, Generator::Braid, 3);
+    let distances = distance_field(&maze, maze.start_cell);
+    let solution = solve(&maze, "bfs").unwrap();
+
+    let visited: Vec<usize> = solution
+        .visited
+        .iter()
+        .map(|(row, col)| distances[row * maze.cols + col].unwrap())
+        .collect();
+
+    assert!(visited.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    // Every cell is visited once.
+    let mut unique = solution.visited.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), solution.visited.len());
+}
+
+#[test]
+fn heuristic_solvers_find_a_path_on_braided_mazes() {
+    for seed in 0..10 {
+        let maze = generated(10, 10, Generator::Braid, seed);
+        let shortest = distance(&maze, maze.start_cell, maze.end_cell);
+
+        for (name, label) in SOLVERS {
+            let solution = solve(&maze, name).unwrap();
+
+            assert_eq!(solution.path.first(), Some(&maze.start_cell));
+            assert_eq!(solution.path.last(), Some(&maze.end_cell));
+            assert!(solution.steps() >= shortest);
+
+            if !["bfs", "backtrack", "dijkstra"].contains(&name) {
+                assert!(label.ends_with("(heuristic)"), "{}", label);
+            }
+        }
+    }
+}
+
+#[test]
+fn braiding_adds_loops() {
+    let mut maze = WilsonMaze::new(10, 10, (0, 0), (9, 9));
+    maze.generate_seeded(Generator::Wilson, 1).unwrap();
+    let perfect = maze.grid.clone();
+
+    let mut rng = StdRng::seed_from_u64(1);
+    braid(&mut maze, 0.0, &mut rng).unwrap();
+    assert_eq!(maze.grid, perfect);
+
+    braid(&mut maze, 1.0, &mut rng).unwrap();
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            assert!(maze.grid.passages(row, col).count() >= 2);
+        }
+    }
+
+    // The braided generator is as reproducible as the others.
+    assert_eq!(
+        generated(8, 9, Generator::Braid, 5).grid,
+        generated(8, 9, Generator::Braid, 5).grid
+    );
+    assert_eq!(Generator::from_name("braid"), Ok(Generator::Braid));
+}
+
+#[test]
+fn solvers_stop_at_the_first_exit() {
+    for seed in 0..10 {
+        let mut maze = generated(12, 15, Generator::Braid, seed);
+        maze.start_cell = (6, 7);
+        maze.exits = vec![(0, 0), (0, 14), (11, 0)];
+        let nearest = maze
+            .ends()
+            .iter()
+            .map(|end| distance(&maze, maze.start_cell, *end))
+            .min()
+            .unwrap();
+
+        for (name, _) in SOLVERS {
+            let solution = solve(&maze, name).unwrap();
+            let reached = *solution.path.last().unwrap();
+
+            assert!(maze.is_end(reached), "{} seed {}", name, seed);
+            // No other end is passed on the way.
+            assert!(solution.path[..solution.path.len() - 1]
+                .iter()
+                .all(|cell| !maze.is_end(*cell)));
+
+            if name == "bfs" || name == "dijkstra" {
+                assert_eq!(solution.steps(), nearest, "{} seed {}", name, seed);
+            }
+        }
+    }
+}
+
+#[test]
+fn backtrack_handles_long_corridors() {
+    // The backtracker generator makes long winding paths, deep enough to
+    // overflow the stack of a test thread with a recursive search.
+    let mut maze = WilsonMaze::new(300, 300, (0, 0), (299, 299));
+    maze.generate_seeded(Generator::Backtracker, 42).unwrap();
+
+    let backtrack = solve(&maze, "backtrack").unwrap();
+    let bfs = solve(&maze, "bfs").unwrap();
+
+    // A perfect maze has a single path between two cells.
+    assert_eq!(backtrack.path, bfs.path);
+}
@@ -0,0 +1,120 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use maze_core::{
+    from_fragment, from_json, get_dijkstra_solution, paint_terrain, path_cost, solve, to_fragment,
+    to_json, Direction, Generator, Terrain, WilsonMaze,
+};
+
+mod common;
+use common::generated;
+
+fn painted(rows: usize, cols: usize, seed: u64) -> WilsonMaze {
+    let mut maze = generated(rows, cols, Generator::Braid, seed);
+    paint_terrain(&mut maze, 8, &mut StdRng::seed_from_u64(seed));
+    return maze;
+}
+
+#[test]
+fn new_mazes_are_all_road() {
+    let maze = WilsonMaze::new(4, 6, (0, 0), (3, 5));
+
+    assert_eq!(maze.terrain.len(), 24);
+    assert!(!maze.has_terrain());
+    assert_eq!(maze.terrain(3, 5), Terrain::Road);
+}
+
+#[test]
+fn path_cost_skips_the_first_cell() {
+    let mut maze = WilsonMaze::new(1, 3, (0, 0), (0, 2));
+    maze.set_terrain(0, 0, Terrain::Water);
+    maze.set_terrain(0, 1, Terrain::Mud);
+
+    assert_eq!(path_cost(&maze, &[(0, 0), (0, 1), (0, 2)]), 3 + 1);
+    assert_eq!(path_cost(&maze, &[(0, 0)]), 0);
+}
+
+#[test]
+fn painting_is_deterministic_for_a_seed() {
+    let a = painted(20, 20, 5);
+    let b = painted(20, 20, 5);
+
+    assert!(a.has_terrain());
+    assert_eq!(a.terrain, b.terrain);
+}
+
+#[test]
+fn dijkstra_goes_around_water() {
+    // A ring of six cells, the short way to the end goes through water.
+    let mut maze = WilsonMaze::new(2, 3, (0, 0), (1, 1));
+    maze.grid.carve(0, 0, &Direction::Right);
+    maze.grid.carve(0, 1, &Direction::Right);
+    maze.grid.carve(0, 2, &Direction::Down);
+    maze.grid.carve(1, 2, &Direction::Left);
+    maze.grid.carve(1, 1, &Direction::Left);
+    maze.grid.carve(1, 0, &Direction::Up);
+    maze.set_terrain(1, 0, Terrain::Water);
+
+    let bfs = solve(&maze, "bfs").unwrap();
+    let dijkstra = get_dijkstra_solution(&maze).unwrap();
+
+    assert_eq!(bfs.path, vec![(0, 0), (1, 0), (1, 1)]);
+    assert_eq!(dijkstra.path, vec![(0, 0), (0, 1), (0, 2), (1, 2), (1, 1)]);
+    assert_eq!(path_cost(&maze, &bfs.path), 6);
+    assert_eq!(path_cost(&maze, &dijkstra.path), 4);
+}
+
+#[test]
+fn dijkstra_is_never_more_expensive_than_bfs() {
+    for seed in 0..20 {
+        let maze = painted(15, 20, seed);
+        let bfs = solve(&maze, "bfs").unwrap();
+        let dijkstra = solve(&maze, "dijkstra").unwrap();
+
+        assert!(path_cost(&maze, &dijkstra.path) <= path_cost(&maze, &bfs.path));
+        assert_eq!(dijkstra.path.first(), Some(&maze.start_cell));
+        assert_eq!(dijkstra.path.last(), Some(&maze.end_cell));
+    }
+}
+
+#[test]
+fn dijkstra_without_terrain_finds_a_shortest_path() {
+    for seed in 0..20 {
+        let maze = generated(15, 20, Generator::Braid, seed);
+
+        let bfs = solve(&maze, "bfs").unwrap();
+        let dijkstra = solve(&maze, "dijkstra").unwrap();
+
+        assert_eq!(dijkstra.steps(), bfs.steps());
+    }
+}
+
+#[test]
+fn terrain_survives_json() {
+    let maze = painted(10, 12, 3);
+
+    let (loaded, _) = from_json(&to_json(&maze, None).unwrap()).unwrap();
+
+    assert_eq!(loaded.terrain, maze.terrain);
+}
+
+#[test]
+fn json_without_terrain_is_all_road() {
+    let maze = generated(5, 5, Generator::Wilson, 1);
+
+    let text = to_json(&maze, None).unwrap();
+    assert!(!text.contains("terrain"));
+
+    let (loaded, _) = from_json(&text).unwrap();
+    assert_eq!(loaded.terrain, vec![Terrain::Road; 25]);
+}
+
+#[test]
+fn terrain_survives_the_fragment() {
+    let maze = painted(10, 12, 3);
+
+    let shared = from_fragment(&to_fragment(&maze, Some("dijkstra"))).unwrap();
+
+    assert_eq!(shared.maze.terrain, maze.terrain);
+    assert_eq!(shared.solver.as_deref(), Some("dijkstra"));
+}
@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+
+use maze_core::{
+    parse_text, render_directions, render_text, solve, Direction, Generator, MazeError,
+    TextOptions, TextStyle, WilsonMaze, SOLVERS,
+};
+
+const FIXTURES: [&str; 2] = [
+    include_str!("fixtures/wilson_5x7.txt"),
+    include_str!("fixtures/backtracker_4x6.txt"),
+];
+
+const UNSOLVABLE: &str = include_str!("fixtures/unsolvable_2x3.txt");
+
+mod common;
+use common::generated;
+
+#[test]
+fn round_trip_both_styles() {
+    for generator in [Generator::Wilson, Generator::Backtracker] {
+        for style in [TextStyle::Ascii, TextStyle::Unicode] {
+            // Away from the corners that parsing defaults to.
+            let mut maze = generated(12, 17, generator, 1);
+            maze.start_cell = (3, 4);
+            maze.end_cell = (11, 0);
+            let options = TextOptions {
+                style: style,
+                ..Default::default()
+            };
+
+            let parsed = parse_text(&render_text(&maze, &options)).unwrap();
+
+            assert_eq!(parsed.maze.grid, maze.grid);
+            assert_eq!(parsed.maze.start_cell, maze.start_cell);
+            assert_eq!(parsed.maze.end_cell, maze.end_cell);
+            assert!(parsed.solution.is_empty());
+            assert!(parsed.visited.is_empty());
+        }
+    }
+}
+
+#[test]
+fn round_trip_overlays() {
+    let maze = generated(12, 17, Generator::Wilson, 2);
+    let run = solve(&maze, "bfs").unwrap();
+    let solution = run.path_cells();
+    let visited: HashSet<(usize, usize)> = run.visited.into_iter().collect();
+
+    for style in [TextStyle::Ascii, TextStyle::Unicode] {
+        let options = TextOptions {
+            style: style,
+            solution: Some(&solution),
+            visited: Some(&visited),
+        };
+
+        let parsed = parse_text(&render_text(&maze, &options)).unwrap();
+
+        let expected_visited: HashSet<(usize, usize)> = visited
+            .difference(&solution)
+            .filter(|cell| **cell != maze.start_cell && **cell != maze.end_cell)
+            .cloned()
+            .collect();
+
+        assert_eq!(parsed.solution, solution);
+        assert_eq!(parsed.visited, expected_visited);
+    }
+}
+
+#[test]
+fn rendering_is_stable() {
+    for fixture in FIXTURES {
+        let parsed = parse_text(fixture).unwrap();
+        let style = match fixture.starts_with('+') {
+            true => TextStyle::Ascii,
+            false => TextStyle::Unicode,
+        };
+        let options = TextOptions {
+            style: style,
+            solution: Some(&parsed.solution),
+            visited: None,
+        };
+
+        assert_eq!(render_text(&parsed.maze, &options), fixture);
+    }
+}
+
+#[test]
+fn solvers_match_fixtures() {
+    for fixture in FIXTURES {
+        let parsed = parse_text(fixture).unwrap();
+
+        for (name, _) in SOLVERS {
+            let solution = solve(&parsed.maze, name).unwrap();
+            assert_eq!(
+                solution.path_cells(),
+                parsed.solution,
+                "solver {} on\n{}",
+                name,
+                fixture
+            );
+        }
+    }
+}
+
+#[test]
+fn solvers_report_unsolvable_fixture() {
+    let parsed = parse_text(UNSOLVABLE).unwrap();
+
+    for (name, _) in SOLVERS {
+        assert_eq!(
+            solve(&parsed.maze, name).unwrap_err(),
+            MazeError::NoSolution,
+            "solver {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn parse_tolerates_surrounding_whitespace() {
+    let text = format!("\n\n{}\n", UNSOLVABLE.replace("|S       |", "|S"));
+    let parsed = parse_text(&text).unwrap();
+
+    assert_eq!(parsed.maze.rows, 2);
+    assert_eq!(parsed.maze.cols, 3);
+    assert_eq!(parsed.maze.start_cell, (0, 0));
+    assert_eq!(parsed.maze.end_cell, (1, 2));
+}
+
+#[test]
+fn parse_rejects_malformed_text() {
+    assert!(parse_text("").is_err());
+    assert!(parse_text("+--+\n|  |\n").is_err());
+    assert!(parse_text("+--+-\n|  |\n+--+-\n").is_err());
+}
+
+#[test]
+fn directions_along_the_solution() {
+    let parsed = parse_text(FIXTURES[0]).unwrap();
+    let solution = solve(&parsed.maze, "bfs").unwrap();
+
+    assert_eq!(solution.steps(), solution.path.len() - 1);
+    assert_eq!(solution.directions().len(), solution.steps());
+
+    // Following the directions from the start ends at the end cell.
+    let mut cell = parsed.maze.start_cell;
+    for line in render_directions(&solution).lines() {
+        let (direction, steps) = line.split_once(' ').unwrap();
+        for _ in 0..steps.parse::<usize>().unwrap() {
+            cell = match direction {
+                "up" => (cell.0 - 1, cell.1),
+                "down" => (cell.0 + 1, cell.1),
+                "left" => (cell.0, cell.1 - 1),
+                "right" => (cell.0, cell.1 + 1),
+                _ => panic!("unknown direction {}", direction),
+            };
+        }
+    }
+    assert_eq!(cell, parsed.maze.end_cell);
+}
+
+#[test]
+fn round_trip_exits() {
+    let mut maze = generated(12, 17, Generator::Wilson, 3);
+    maze.exits = vec![(0, 16), (5, 8)];
+    let run = solve(&maze, "bfs").unwrap();
+    let solution = run.path_cells();
+
+    let options = TextOptions {
+        solution: Some(&solution),
+        ..Default::default()
+    };
+    let text = render_text(&maze, &options);
+    assert_eq!(text.matches('E').count(), 3);
+
+    let parsed = parse_text(&text).unwrap();
+
+    let mut ends = parsed.maze.ends();
+    let mut expected = maze.ends();
+    ends.sort();
+    expected.sort();
+    assert_eq!(ends, expected);
+    assert!(parsed.solution.contains(run.path.last().unwrap()));
+}
+
+#[test]
+fn solution_only_includes_the_end_it_reaches() {
+    // The path goes down the left column to the exit, away from the end.
+    let mut maze = WilsonMaze::new(3, 3, (0, 0), (0, 2));
+    maze.exits = vec![(2, 0)];
+    for (row, col, direction) in [
+        (0, 0, Direction::Down),
+        (1, 0, Direction::Down),
+        (0, 0, Direction::Right),
+        (0, 1, Direction::Right),
+        (2, 0, Direction::Right),
+        (2, 1, Direction::Right),
+        (1, 1, Direction::Down),
+    ] {
+        maze.grid.carve(row, col, &direction);
+    }
+
+    let solution = solve(&maze, "bfs").unwrap();
+    assert_eq!(solution.path, vec![(0, 0), (1, 0), (2, 0)]);
+
+    let cells = solution.path_cells();
+    let options = TextOptions {
+        solution: Some(&cells),
+        ..Default::default()
+    };
+    let parsed = parse_text(&render_text(&maze, &options)).unwrap();
+
+    assert_eq!(parsed.solution, cells);
+}
+
+#[test]
+fn round_trip_start_on_the_end() {
+    for style in [TextStyle::Ascii, TextStyle::Unicode] {
+        let mut maze = WilsonMaze::new(3, 3, (1, 1), (1, 1));
+        maze.generate_seeded(Generator::Wilson, 1).unwrap();
+        let options = TextOptions {
+            style: style,
+            ..Default::default()
+        };
+
+        let text = render_text(&maze, &options);
+        let parsed = parse_text(&text).unwrap();
+
+        assert!(text.contains("SE"));
+        assert_eq!(parsed.maze.start_cell, (1, 1));
+        assert_eq!(parsed.maze.end_cell, (1, 1));
+        assert!(parsed.maze.exits.is_empty());
+    }
+}
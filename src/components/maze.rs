@@ -1,11 +1,44 @@
 use dioxus::prelude::*;
 
-use crate::utils::{get_backtrack_solution, get_bfs_solution, Direction, Priority, WilsonMaze};
+use super::maze_svg::{maze_svg, SVG_CELL_THRESHOLD};
+use maze_core::{
+    analyze, clamp_cell, diameter, difficulty_range, distance_field, farthest_cell, from_json,
+    generate_with_difficulty, gradient_colour, paint_terrain, path_cost, solve, to_json, Colours,
+    Direction, Generator, Grid, MazeError, SolverRun, Terrain, WilsonMaze, DIFFICULTIES, SOLVERS,
+    TERRAINS,
+};
 use std::collections::HashSet;
+
+#[cfg(feature = "web")]
+use maze_core::{from_fragment, to_fragment};
+
+const MIN_DIM: usize = 2;
+const MAX_DIM: usize = 500;
 const NROWS_PLACEHOLDER: usize = 10;
 const NCOLS_PLACEHOLDER: usize = 10;
 const START_PLACEHOLDER: (usize, usize) = (0, 0);
 const END_PLACEHOLDER: (usize, usize) = (9, 9);
+/// Most mazes to try when randomizing with a difficulty, before giving up.
+const DIFFICULTY_ATTEMPTS: usize = 200;
+/// Cells to generate and analyze in total when randomizing with a
+/// difficulty. The search runs on the UI thread, so large mazes get fewer
+/// attempts to keep it from hanging.
+const DIFFICULTY_CELL_BUDGET: usize = 250_000;
+
+/// Number of attempts for a maze of the given size, at least one.
+pub fn difficulty_attempts(rows: usize, cols: usize) -> usize {
+    return (DIFFICULTY_CELL_BUDGET / std::cmp::max(rows * cols, 1)).clamp(1, DIFFICULTY_ATTEMPTS);
+}
+
+/// Parse a row/column count from the number input. Values outside of
+/// [MIN_DIM, MAX_DIM] are clamped, and anything that is not a number
+/// keeps the current value.
+pub fn parse_dim(value: &str, current: usize) -> usize {
+    match value.trim().parse::<usize>() {
+        Ok(v) => return v.clamp(MIN_DIM, MAX_DIM),
+        Err(_) => return current,
+    }
+}
 
 /// General TODO for entire project:
 /// * Smaller things:
@@ -19,41 +52,41 @@ const END_PLACEHOLDER: (usize, usize) = (9, 9);
 ///
 /// * Performance:
 ///     ** Visited should actually be (visited - solution).
-///     ** Rework maze class with how we generate and store grid (and solution).
+///     ** Rework maze class with how we store the solution.
 ///
 ///
 pub fn get_class_name(
-    grid: &Vec<Vec<HashSet<Direction>>>,
+    grid: &Grid,
     row: usize,
     col: usize,
     is_solution: bool,
     is_visit: bool,
+    is_farthest: bool,
+    terrain: Terrain,
 ) -> String {
-    let cell = &grid[row][col];
-
-    let last_row = grid.len() - 1;
-    let last_col = grid[0].len() - 1;
+    let last_row = grid.rows - 1;
+    let last_col = grid.cols - 1;
 
-    if cell.len() == 0 {
+    if grid.is_closed(row, col) {
         return "maze-cell no".to_string();
     }
 
     let mut directions = "maze-cell ".to_string();
 
-    if cell.contains(&Direction::Left) || (row == 0 && col == 0) {
+    if grid.has_passage(row, col, &Direction::Left) || (row == 0 && col == 0) {
         directions.push('l');
     }
 
     // We need to fix this as well for the end cell.
-    if cell.contains(&Direction::Right) || (row == last_row && col == last_col) {
+    if grid.has_passage(row, col, &Direction::Right) || (row == last_row && col == last_col) {
         directions.push('r');
     }
 
-    if cell.contains(&Direction::Up) {
+    if grid.has_passage(row, col, &Direction::Up) {
         directions.push('u');
     }
 
-    if cell.contains(&Direction::Down) {
+    if grid.has_passage(row, col, &Direction::Down) {
         directions.push('d');
     }
 
@@ -73,12 +106,35 @@ pub fn get_class_name(
         directions.push_str(" visited");
     }
 
+    if is_farthest {
+        directions.push_str(" farthest");
+    }
+
+    if terrain != Terrain::Road {
+        directions.push(' ');
+        directions.push_str(terrain.name());
+    }
+
     return directions;
 }
 
+/// Inline background colour of a cell in the distance heatmap, from blue
+/// next to the origin to orange at the farthest cell. Empty if the cell is
+/// not reachable or the heatmap is hidden.
+pub fn get_heat_style(distance: Option<usize>, max_distance: usize) -> String {
+    match distance {
+        Some(distance) => {
+            let t = distance as f32 / std::cmp::max(max_distance, 1) as f32;
+            let [r, g, b, _] = gradient_colour(&Colours::default(), t);
+            return format!("background-color: rgb({}, {}, {});", r, g, b);
+        }
+        None => return String::new(),
+    }
+}
+
 #[component]
 pub fn Maze() -> Element {
-    // We need signals and use effects for rows and columns
+    // We need signals for rows and columns
     let mut nrows: Signal<usize> = use_signal(|| NROWS_PLACEHOLDER);
     let mut ncols: Signal<usize> = use_signal(|| NCOLS_PLACEHOLDER);
     let mut start_cell: Signal<(usize, usize)> = use_signal(|| START_PLACEHOLDER);
@@ -88,35 +144,153 @@ pub fn Maze() -> Element {
     let mut solution: Signal<HashSet<(usize, usize)>> = use_signal(|| HashSet::new());
     let mut visited: Signal<HashSet<(usize, usize)>> = use_signal(|| HashSet::new());
     let mut solver: Signal<String> = use_signal(|| "bfs".to_string());
+    let mut error: Signal<Option<String>> = use_signal(|| None);
+    // Empty for any difficulty, otherwise one of DIFFICULTIES.
+    let mut difficulty: Signal<String> = use_signal(|| String::new());
+
+    // Distance heatmap, measured from the clicked cell or else the start.
+    let mut show_distances: Signal<bool> = use_signal(|| false);
+    let mut distance_origin: Signal<Option<(usize, usize)>> = use_signal(|| None);
+
+    // While set, clicking a cell adds or removes it as an exit.
+    let mut placing_exits: Signal<bool> = use_signal(|| false);
+
+    // Last solver run, kept in visit order so that it can be saved.
+    let mut run: Signal<Option<SolverRun>> = use_signal(|| None);
+    let mut json_text: Signal<String> = use_signal(|| String::new());
 
     let mut maze: Signal<WilsonMaze> = use_signal(|| {
-        WilsonMaze::new(
+        let mut m = WilsonMaze::new(
             NROWS_PLACEHOLDER,
             NCOLS_PLACEHOLDER,
             START_PLACEHOLDER,
             END_PLACEHOLDER,
-        )
+        );
+        // The placeholder cells are always within bounds.
+        m.generate().unwrap();
+        m
     });
 
-    // When changing cols or rows, we need to update the maze
-    // and also set the solution to empty.
-    use_effect(move || {
-        let mut m = WilsonMaze::new(
-            *nrows.read(),
-            *ncols.read(),
-            *start_cell.read(),
-            *end_cell.read(),
-        );
-        m.generate();
-        maze.set(m);
-        solution.set(HashSet::new());
-        visited.set(HashSet::new());
+    // When changing cols, rows or start/end cells, we need to generate a
+    // new maze and also set the solution to empty. This is done explicitly
+    // rather than in an effect, so that loading a saved maze does not
+    // trigger a new one to be generated.
+    let mut regenerate =
+        move |rows: usize, cols: usize, start: (usize, usize), end: (usize, usize)| {
+            let start = clamp_cell(start, rows, cols);
+            let end = clamp_cell(end, rows, cols);
+
+            nrows.set(rows);
+            ncols.set(cols);
+            start_cell.set(start);
+            end_cell.set(end);
+
+            let mut m = WilsonMaze::new(rows, cols, start, end);
+            match m.generate() {
+                Ok(()) => error.set(None),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            maze.set(m);
+            solution.set(HashSet::new());
+            visited.set(HashSet::new());
+            run.set(None);
+            distance_origin.set(None);
+        };
+
+    // Only recomputed when the maze changes, not on every solve.
+    let metrics = use_memo(move || analyze(&maze.read()));
+
+    // Distances from the origin and the farthest cell, only while the
+    // heatmap is shown. An origin from a previous, larger maze is ignored.
+    let distances = use_memo(move || {
+        let m = maze.read();
+        let origin = distance_origin
+            .read()
+            .filter(|(row, col)| *row < m.rows && *col < m.cols)
+            .unwrap_or(m.start_cell);
+
+        match *show_distances.read() {
+            true => return (distance_field(&m, origin), Some(farthest_cell(&m, origin))),
+            false => return (vec![], None),
+        }
     });
 
+    // On the web, the maze and the selected solver are kept in the url
+    // fragment, so that a link to the page opens the same maze. Nothing is
+    // written until the fragment the page was opened with has been read.
+    #[cfg(feature = "web")]
+    {
+        let mut restored: Signal<bool> = use_signal(|| false);
+
+        use_hook(move || {
+            spawn(async move {
+                let hash = document::eval("return window.location.hash;")
+                    .join::<String>()
+                    .await
+                    .unwrap_or_default();
+
+                if !hash.trim_start_matches('#').is_empty() {
+                    match from_fragment(&hash) {
+                        Ok(shared) => {
+                            nrows.set(shared.maze.rows);
+                            ncols.set(shared.maze.cols);
+                            start_cell.set(shared.maze.start_cell);
+                            end_cell.set(shared.maze.end_cell);
+                            maze.set(shared.maze);
+                            if let Some(s) = shared.solver {
+                                solver.set(s);
+                            }
+                            solution.set(HashSet::new());
+                            visited.set(HashSet::new());
+                            run.set(None);
+                        }
+                        Err(e) => error.set(Some(format!("Could not open the link: {}", e))),
+                    }
+                }
+
+                restored.set(true);
+            });
+        });
+
+        use_effect(move || {
+            let fragment = to_fragment(&maze.read(), Some(&solver.read()));
+            if *restored.read() {
+                document::eval(&format!("history.replaceState(null, '', '#{}');", fragment));
+            }
+        });
+    }
+
+    // Clicking a cell places exits, or else moves the distance origin.
+    let mut click_cell = move |cell: (usize, usize)| {
+        if *placing_exits.read() {
+            {
+                let mut m = maze.write();
+                if cell == m.end_cell {
+                    return;
+                }
+                match m.exits.iter().position(|exit| *exit == cell) {
+                    Some(i) => {
+                        m.exits.remove(i);
+                    }
+                    None => m.exits.push(cell),
+                }
+            }
+            solution.set(HashSet::new());
+            visited.set(HashSet::new());
+            run.set(None);
+        } else if *show_distances.read() {
+            distance_origin.set(Some(cell));
+        }
+    };
+
     let m = &maze.read();
+    let ends = m.ends();
 
     let sol = &solution.read();
     let visit = &visited.read();
+    let (heat, farthest) = &*distances.read();
+    let max_distance = farthest.map(|(_, steps)| steps).unwrap_or(0);
+    let farthest_cell_pos = farthest.map(|(cell, _)| cell);
 
     rsx! {
         div { id: "container",
@@ -127,118 +301,278 @@ pub fn Maze() -> Element {
                     label { r#for: "row-input", "Rows: " }
                     input {
                         id: "row-input",
-                        r#type: "range",
-                        value: "10",
-                        min: "10",
-                        max: "40",
-                        step: "10",
-                        list: "row-markers",
-                        class: "slider",
+                        r#type: "number",
+                        value: "{nrows}",
+                        min: "{MIN_DIM}",
+                        max: "{MAX_DIM}",
+                        step: "1",
                         onchange: move |evt| {
-                            let r: usize = evt.value().parse().unwrap();
-                            nrows.set(evt.value().parse().unwrap());
-                            start_cell.set((0, 0));
-                            let (_, end_c) = *end_cell.read();
-                            end_cell.set((r - 1, end_c));
+                            let r = parse_dim(&evt.value(), *nrows.read());
+                            let c = *ncols.read();
+                            regenerate(r, c, (0, 0), (r - 1, c - 1));
                         },
                     }
-                    datalist { id: "row-markers",
-                        option { value: "10" }
-                        option { value: "20" }
-                        option { value: "30" }
-                        option { value: "40" }
-                    }
-                    span { id: "row-input-span", "{nrows}" }
                 }
 
                 div { id: "col-input-container",
                     label { r#for: "col-input", "Columns: " }
                     input {
                         id: "col-input",
-                        r#type: "range",
-                        value: "10",
-                        min: "10",
-                        max: "40",
-                        step: "10",
-                        list: "col-markers",
-                        class: "slider",
+                        r#type: "number",
+                        value: "{ncols}",
+                        min: "{MIN_DIM}",
+                        max: "{MAX_DIM}",
+                        step: "1",
                         onchange: move |evt| {
-                            let c: usize = evt.value().parse().unwrap();
-                            ncols.set(c);
-                            start_cell.set((0, 0));
-                            let (end_r, _): (usize, usize) = *end_cell.read();
-                            end_cell.set((end_r, c - 1));
+                            let r = *nrows.read();
+                            let c = parse_dim(&evt.value(), *ncols.read());
+                            regenerate(r, c, (0, 0), (r - 1, c - 1));
                         },
                     }
-                    datalist { id: "col-markers",
-                        option { value: "10" }
-                        option { value: "20" }
-                        option { value: "30" }
-                        option { value: "40" }
-                    }
-                    span { id: "col-input-span", "{ncols}" }
                 }
 
+                span { id: "dim-input-span", "({MIN_DIM} - {MAX_DIM})" }
             }
 
 
 
 
-            // We might streamline this with some kind of
-            // enum of a vec of options.
             div { id: "solver-container",
                 label { id: "solver-label", r#for: "solver", "Choose a solver:" }
                 select {
                     id: "solver",
                     name: "solver",
+                    value: "{solver}",
                     onchange: move |evt| {
                         solver.set(evt.value());
                     },
-                    option { value: "bfs", "BFS Default" }
-                    option { value: "bfs-prio", "BFS Prio" }
-                    option { value: "bfs-close", "BFS Close" }
-                    option { value: "bfs-random", "BFS Random" }
-                    option { value: "backtrack", "DFS Backtrack" }
-
+                    for (name, label) in SOLVERS {
+                        option { value: name, "{label}" }
+                    }
                 }
 
             }
 
+            div { id: "difficulty-container",
+                label {
+                    id: "difficulty-label",
+                    r#for: "difficulty",
+                    "Difficulty:"
+                }
+                select {
+                    id: "difficulty",
+                    name: "difficulty",
+                    value: "{difficulty}",
+                    onchange: move |evt| {
+                        difficulty.set(evt.value());
+                    },
+                    option { value: "", "Any" }
+                    for (name, _, _) in DIFFICULTIES {
+                        option { value: name, "{name}" }
+                    }
+                }
+            }
+
             button {
                 id: "switch-btn",
                 onclick: move |_| {
                     let r = *nrows.read();
                     let c = *ncols.read();
+
+                    // Keep generating until the difficulty is in range, so
+                    // the start and end cells are picked along with the maze.
+                    if let Some(range) = difficulty_range(&difficulty.read()) {
+                        let generated = generate_with_difficulty(
+                            r,
+                            c,
+                            Generator::Wilson,
+                            range,
+                            difficulty_attempts(r, c),
+                            &mut rand::rng(),
+                        );
+                        match generated {
+                            Ok(m) => {
+                                start_cell.set(m.start_cell);
+                                end_cell.set(m.end_cell);
+                                maze.set(m);
+                                solution.set(HashSet::new());
+                                visited.set(HashSet::new());
+                                run.set(None);
+                                error.set(None);
+                            }
+                            Err(MazeError::DifficultyNotFound { attempts }) => {
+                                error.set(
+                                    Some(
+                                        format!(
+                                            "No {} maze was found in {} attempts, so the maze \
+                                             was not changed. Try again, or pick a smaller \
+                                             size for more attempts.",
+                                            difficulty.read(),
+                                            attempts,
+                                        ),
+                                    ),
+                                )
+                            }
+                            Err(e) => error.set(Some(e.to_string())),
+                        }
+                        return;
+                    }
+
                     let start_r = rand::random_range(0..r);
                     let start_c = rand::random_range(0..c);
-                    start_cell.set((start_r, start_c));
                     let mut end_r = rand::random_range(0..r);
                     let mut end_c = rand::random_range(0..c);
                     while (start_r, start_c) == (end_r, end_c) {
                         end_r = rand::random_range(0..r);
                         end_c = rand::random_range(0..c);
                     }
-                    end_cell.set((end_r, end_c));
+                    regenerate(r, c, (start_r, start_c), (end_r, end_c));
                 },
                 "Randomize"
             }
 
+            div { id: "distance-container",
+                label { id: "distance-label",
+                    input {
+                        id: "distance-toggle",
+                        r#type: "checkbox",
+                        checked: *show_distances.read(),
+                        onchange: move |evt| {
+                            show_distances.set(evt.checked());
+                            distance_origin.set(None);
+                        },
+                    }
+                    "Show distances"
+                }
+
+                button {
+                    id: "farthest-btn",
+                    onclick: move |_| {
+                        // The grid does not depend on the end cell, so the
+                        // maze is kept and only the end is moved.
+                        let (end, _) = farthest_cell(&maze.read(), *start_cell.read());
+                        maze.write().end_cell = end;
+                        end_cell.set(end);
+                        solution.set(HashSet::new());
+                        visited.set(HashSet::new());
+                        run.set(None);
+                    },
+                    "End at farthest"
+                }
+
+                button {
+                    id: "longest-btn",
+                    onclick: move |_| {
+                        let (start, end, _) = diameter(&maze.read());
+                        {
+                            let mut m = maze.write();
+                            m.start_cell = start;
+                            m.end_cell = end;
+                            // The generators start from the start cell, so
+                            // the seed would no longer give the same maze.
+                            m.generator = None;
+                            m.seed = None;
+                        }
+                        start_cell.set(start);
+                        end_cell.set(end);
+                        solution.set(HashSet::new());
+                        visited.set(HashSet::new());
+                        run.set(None);
+                        distance_origin.set(None);
+                    },
+                    "Longest path"
+                }
+            }
+
+            div { id: "terrain-container",
+                button {
+                    id: "paint-terrain-btn",
+                    onclick: move |_| {
+                        let regions = {
+                            let m = maze.read();
+                            (m.rows * m.cols / 40).clamp(1, 30)
+                        };
+                        paint_terrain(&mut maze.write(), regions, &mut rand::rng());
+                        solution.set(HashSet::new());
+                        visited.set(HashSet::new());
+                        run.set(None);
+                    },
+                    "Paint terrain"
+                }
+
+                button {
+                    id: "clear-terrain-btn",
+                    onclick: move |_| {
+                        let cells = maze.read().terrain.len();
+                        maze.write().terrain = vec![Terrain::Road; cells];
+                        solution.set(HashSet::new());
+                        visited.set(HashSet::new());
+                        run.set(None);
+                    },
+                    "Clear terrain"
+                }
+
+                span { id: "terrain-legend",
+                    for terrain in TERRAINS {
+                        span { class: "terrain-key {terrain.name()}",
+                            "{terrain.name()} ({terrain.cost()})"
+                        }
+                    }
+                }
+            }
+
+            div { id: "exit-container",
+                label { id: "exit-label",
+                    input {
+                        id: "exit-toggle",
+                        r#type: "checkbox",
+                        checked: *placing_exits.read(),
+                        onchange: move |evt| placing_exits.set(evt.checked()),
+                    }
+                    "Click to add exits"
+                }
+
+                button {
+                    id: "clear-exits-btn",
+                    onclick: move |_| {
+                        maze.write().exits.clear();
+                        solution.set(HashSet::new());
+                        visited.set(HashSet::new());
+                        run.set(None);
+                    },
+                    "Clear exits"
+                }
+
+                span { id: "exit-count",
+                    "{ends.len()} exits, solvers stop at the first one reached."
+                }
+            }
+
 
 
             div { id: "btn-row",
                 button {
                     id: "solve-btn",
                     onclick: move |_| {
-                        let (maze_solution, maze_visited) = match &solver.read().as_str() {
-                            &"bfs" => get_bfs_solution(&maze.read(), Priority::Disabled),
-                            &"bfs-prio" => get_bfs_solution(&maze.read(), Priority::Prio),
-                            &"bfs-close" => get_bfs_solution(&maze.read(), Priority::Close),
-                            &"bfs-random" => get_bfs_solution(&maze.read(), Priority::Random),
-                            &"backtrack" => get_backtrack_solution(&maze.read()),
-                            _ => panic!("Invalid solver method."),
-                        };
-                        solution.set(maze_solution);
-                        visited.set(maze_visited);
+                        match solve(&maze.read(), &solver.read()) {
+                            Ok(maze_solution) => {
+                                solution.set(maze_solution.path_cells());
+                                visited.set(maze_solution.visited.iter().cloned().collect());
+                                run.set(
+                                    Some(SolverRun {
+                                        solver: solver.read().clone(),
+                                        solution: maze_solution,
+                                    }),
+                                );
+                                error.set(None);
+                            }
+                            Err(e) => {
+                                solution.set(HashSet::new());
+                                visited.set(HashSet::new());
+                                run.set(None);
+                                error.set(Some(e.to_string()));
+                            }
+                        }
                     },
                     "Solve"
                 }
@@ -246,49 +580,152 @@ pub fn Maze() -> Element {
                 button {
                     id: "reset-btn",
                     onclick: move |_| {
-                        solution.set(HashSet::new());
-                        visited.set(HashSet::new());
-                        start_cell.set((0, 0));
+                        let r = *nrows.read();
                         let c = *ncols.read();
-                        end_cell.set((c - 1, c - 1));
+                        regenerate(r, c, (0, 0), (r - 1, c - 1));
                     },
                     "Reset"
                 }
             }
 
+            div { id: "json-container",
+                div { id: "json-btn-row",
+                    button {
+                        id: "save-btn",
+                        onclick: move |_| {
+                            match to_json(&maze.read(), run.read().as_ref()) {
+                                Ok(text) => json_text.set(text),
+                                Err(e) => error.set(Some(e.to_string())),
+                            }
+                        },
+                        "Save"
+                    }
+
+                    button {
+                        id: "load-btn",
+                        onclick: move |_| {
+                            let loaded = from_json(&json_text.read());
+                            match loaded {
+                                Ok((m, maze_run)) => {
+                                    nrows.set(m.rows);
+                                    ncols.set(m.cols);
+                                    start_cell.set(m.start_cell);
+                                    end_cell.set(m.end_cell);
+                                    maze.set(m);
+                                    match &maze_run {
+                                        Some(r) => {
+                                            solver.set(r.solver.clone());
+                                            solution.set(r.solution.path_cells());
+                                            visited.set(r.solution.visited.iter().cloned().collect());
+                                        }
+                                        None => {
+                                            solution.set(HashSet::new());
+                                            visited.set(HashSet::new());
+                                        }
+                                    }
+                                    run.set(maze_run);
+                                    error.set(None);
+                                }
+                                Err(e) => error.set(Some(e.to_string())),
+                            }
+                        },
+                        "Load"
+                    }
+                }
+
+                textarea {
+                    id: "json-text",
+                    placeholder: "Press Save to get the maze as JSON, or paste a saved maze and press Load.",
+                    value: "{json_text}",
+                    oninput: move |evt| json_text.set(evt.value()),
+                }
+            }
+
+            if let Some(e) = error.read().as_ref() {
+                div { id: "error-message", "{e}" }
+            }
+
             div { id: "num-iterations",
 
                 match visit.len() {
                     0 => format!("Maze is unsolved..."),
                     _ => {
+                        let cost = match (m.has_terrain(), run.read().as_ref()) {
+                            (true, Some(r)) => {
+                                format!(" The path costs {}.", path_cost(m, &r.solution.path))
+                            }
+                            _ => String::new(),
+                        };
                         format!(
-                            "Solved in {} iterations ({}% of maze searched).",
+                            "Solved in {} iterations ({}% of maze searched).{}",
                             visit.len(),
                             (100 as f32 * (visit.len() as f32 / (ncols * *nrows.read()) as f32))
                                 as usize,
+                            cost,
                         )
                     }
                 }
             }
 
+            if let Some(((row, col), steps)) = farthest {
+                div { id: "distance-info",
+                    "Farthest cell is ({row}, {col}), {steps} steps away. Click a cell to measure from there."
+                }
+            }
+
+            details { id: "metrics-panel",
+                summary { "Maze analysis" }
+                table { id: "metrics-table",
+                    for (label, value) in metrics.read().rows() {
+                        tr {
+                            td { class: "metrics-label", "{label}" }
+                            td { class: "metrics-value", "{value}" }
+                        }
+                    }
+                }
+            }
+
             div { id: "maze-container",
 
-                for row in 0..maze.read().rows {
-                    div { id: "maze-row",
-                        for col in 0..maze.read().cols {
-                            div {
-                                class: get_class_name(
-                                    &m.grid,
-                                    row,
-                                    col,
-                                    sol.contains(&(row, col)),
-                                    visit.contains(&(row, col)),
-                                ),
-                                if (row, col) == *start_cell.read() {
-                                    span { id: "start-cell", "S" }
-                                }
-                                if (row, col) == *end_cell.read() {
-                                    span { id: "end-cell", "E" }
+                if m.rows * m.cols > SVG_CELL_THRESHOLD {
+                    {
+                        maze_svg(
+                            m,
+                            sol,
+                            visit,
+                            heat,
+                            farthest_cell_pos,
+                            *start_cell.read(),
+                            click_cell,
+                        )
+                    }
+                } else {
+                    for row in 0..maze.read().rows {
+                        div { id: "maze-row",
+                            for col in 0..maze.read().cols {
+                                div {
+                                    class: get_class_name(
+                                        &m.grid,
+                                        row,
+                                        col,
+                                        sol.contains(&(row, col)),
+                                        visit.contains(&(row, col)),
+                                        farthest_cell_pos == Some((row, col)),
+                                        m.terrain(row, col),
+                                    ),
+                                    style: get_heat_style(
+                                        heat.get(row * m.cols + col).cloned().flatten(),
+                                        max_distance,
+                                    ),
+                                    onclick: move |_| click_cell((row, col)),
+                                    if (row, col) == *start_cell.read() {
+                                        span { id: "start-cell", "S" }
+                                    }
+                                    if (row, col) == *end_cell.read() {
+                                        span { id: "end-cell", "E" }
+                                    } else if m.exits.contains(&(row, col)) {
+                                        span { class: "exit-cell", "E" }
+                                    }
                                 }
                             }
                         }
@@ -0,0 +1,193 @@
+use dioxus::prelude::*;
+
+use maze_core::{gradient_colour, Colours, Direction, Terrain, WilsonMaze};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Above this many cells, we render the maze as a single svg instead of
+/// one div per cell, which quickly becomes sluggish for large grids.
+pub const SVG_CELL_THRESHOLD: usize = 1600;
+
+/// Approximate size of the rendered svg (in px) along its longest side.
+const SVG_MAX_SIZE: usize = 900;
+
+/// Margin (in cells) around the maze in the svg viewBox, so the outer walls
+/// are not cut off.
+const SVG_PADDING: f64 = 0.1;
+
+/// Build an svg path of all walls, with one unit per cell. Like the div
+/// renderer, the outer walls at (0, 0) and the last cell are left open.
+pub fn wall_path(maze: &WilsonMaze) -> String {
+    let mut d = String::new();
+
+    // Top border, and left border except for the entrance.
+    write!(d, "M0 0h{}M0 1v{}", maze.cols, maze.rows - 1).unwrap();
+
+    for row in 0..maze.rows {
+        for col in 0..maze.cols {
+            let is_exit = row == maze.rows - 1 && col == maze.cols - 1;
+
+            if !maze.grid.has_passage(row, col, &Direction::Right) && !is_exit {
+                write!(d, "M{} {}v1", col + 1, row).unwrap();
+            }
+
+            if !maze.grid.has_passage(row, col, &Direction::Down) {
+                write!(d, "M{} {}h1", col, row + 1).unwrap();
+            }
+        }
+    }
+
+    return d;
+}
+
+/// Build an svg path of unit squares, one for each cell.
+pub fn cells_path<'a>(cells: impl Iterator<Item = &'a (usize, usize)>) -> String {
+    let mut d = String::new();
+
+    for (row, col) in cells {
+        write!(d, "M{col} {row}h1v1h-1z").unwrap();
+    }
+
+    return d;
+}
+
+/// Number of colours the distance heatmap is drawn with, one path each.
+const HEAT_BANDS: usize = 16;
+
+/// Build one svg path per band of the distance heatmap, from the cells
+/// next to the origin to the farthest ones, with the colour of each band.
+pub fn heat_paths(maze: &WilsonMaze, distances: &[Option<usize>]) -> Vec<(String, String)> {
+    let max_distance = std::cmp::max(distances.iter().flatten().copied().max().unwrap_or(0), 1);
+
+    let mut bands: Vec<Vec<(usize, usize)>> = vec![vec![]; HEAT_BANDS];
+    for (i, distance) in distances.iter().enumerate() {
+        if let Some(distance) = distance {
+            let band = distance * (HEAT_BANDS - 1) / max_distance;
+            bands[band].push((i / maze.cols, i % maze.cols));
+        }
+    }
+
+    return bands
+        .iter()
+        .enumerate()
+        .filter(|(_, cells)| !cells.is_empty())
+        .map(|(band, cells)| {
+            let t = band as f32 / (HEAT_BANDS - 1) as f32;
+            let [r, g, b, _] = gradient_colour(&Colours::default(), t);
+            (
+                format!("rgb({}, {}, {})", r, g, b),
+                cells_path(cells.iter()),
+            )
+        })
+        .collect();
+}
+
+/// Cell under the point (`x`, `y`) in px from the top left of an svg of
+/// `width` by `height` px, or None outside the maze. The padded viewBox is
+/// scaled uniformly and centred, like the default `xMidYMid meet`.
+fn cell_at(
+    rows: usize,
+    cols: usize,
+    (width, height): (f64, f64),
+    (x, y): (f64, f64),
+) -> Option<(usize, usize)> {
+    let view_width = cols as f64 + 2.0 * SVG_PADDING;
+    let view_height = rows as f64 + 2.0 * SVG_PADDING;
+    let scale = f64::min(width / view_width, height / view_height);
+
+    let col = (x - (width - view_width * scale) / 2.0) / scale - SVG_PADDING;
+    let row = (y - (height - view_height * scale) / 2.0) / scale - SVG_PADDING;
+
+    if col < 0.0 || row < 0.0 || col >= cols as f64 || row >= rows as f64 {
+        return None;
+    }
+
+    return Some((row.floor() as usize, col.floor() as usize));
+}
+
+/// Render the maze, visited cells and solution as a single svg element,
+/// with a marker on every end. Clicking a cell calls `on_cell_click`.
+pub fn maze_svg(
+    maze: &WilsonMaze,
+    solution: &HashSet<(usize, usize)>,
+    visited: &HashSet<(usize, usize)>,
+    distances: &[Option<usize>],
+    farthest: Option<(usize, usize)>,
+    start_cell: (usize, usize),
+    mut on_cell_click: impl FnMut((usize, usize)) + 'static,
+) -> Element {
+    let cell_size = std::cmp::max(SVG_MAX_SIZE / std::cmp::max(maze.rows, maze.cols), 1);
+    let width = cell_size * maze.cols;
+    let height = cell_size * maze.rows;
+
+    // Visited should not be drawn on top of the solution.
+    let visited_d = cells_path(visited.iter().filter(|cell| !solution.contains(cell)));
+    let solution_d = cells_path(solution.iter());
+    let heat = heat_paths(maze, distances);
+    let terrain_d = |terrain: Terrain| {
+        let cells: Vec<(usize, usize)> = (0..maze.rows)
+            .flat_map(|row| (0..maze.cols).map(move |col| (row, col)))
+            .filter(|(row, col)| maze.terrain(*row, *col) == terrain)
+            .collect();
+        cells_path(cells.iter())
+    };
+    let (mud_d, water_d) = (terrain_d(Terrain::Mud), terrain_d(Terrain::Water));
+    let (rows, cols) = (maze.rows, maze.cols);
+    let ends = maze.ends();
+    let view_box = format!(
+        "{} {} {} {}",
+        -SVG_PADDING,
+        -SVG_PADDING,
+        cols as f64 + 2.0 * SVG_PADDING,
+        rows as f64 + 2.0 * SVG_PADDING
+    );
+
+    rsx! {
+        svg {
+            id: "maze-svg",
+            width: "{width}",
+            height: "{height}",
+            view_box: view_box,
+            onclick: move |evt| {
+                let point = evt.element_coordinates();
+                let size = (width as f64, height as f64);
+                if let Some(cell) = cell_at(rows, cols, size, (point.x, point.y)) {
+                    on_cell_click(cell);
+                }
+            },
+            path { class: "svg-mud", d: mud_d }
+            path { class: "svg-water", d: water_d }
+            for (colour, d) in heat {
+                path { fill: colour, d: d }
+            }
+            path { class: "svg-visited", d: visited_d }
+            path { class: "svg-solution", d: solution_d }
+            rect {
+                class: "svg-start",
+                x: "{start_cell.1 as f32 + 0.25}",
+                y: "{start_cell.0 as f32 + 0.25}",
+                width: "0.5",
+                height: "0.5",
+            }
+            for (row, col) in ends {
+                rect {
+                    class: "svg-end",
+                    x: "{col as f32 + 0.25}",
+                    y: "{row as f32 + 0.25}",
+                    width: "0.5",
+                    height: "0.5",
+                }
+            }
+            if let Some((row, col)) = farthest {
+                rect {
+                    class: "svg-farthest",
+                    x: "{col}",
+                    y: "{row}",
+                    width: "1",
+                    height: "1",
+                }
+            }
+            path { class: "svg-walls", d: wall_path(maze) }
+        }
+    }
+}
@@ -4,3 +4,4 @@
 
 mod maze;
 pub use maze::Maze;
+mod maze_svg;
@@ -3,7 +3,6 @@ use dioxus::prelude::*;
 use components::Maze;
 
 mod components;
-mod utils;
 
 // The asset macro also minifies some assets like CSS and JS to make bundled smaller
 const MAIN_CSS: Asset = asset!("/assets/styling/main.css");